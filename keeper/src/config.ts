@@ -39,6 +39,9 @@ export const config = {
   dcaCheckInterval: parseInt(process.env.DCA_CHECK_INTERVAL || '60'),
   intentCheckInterval: parseInt(process.env.INTENT_CHECK_INTERVAL || '30'),
   
+  // Shielded intents: owner-provided trigger reveals (see shielded-reveals.ts)
+  shieldedRevealsPath: process.env.SHIELDED_REVEALS_PATH || '',
+  
   // Logging
   logLevel: process.env.LOG_LEVEL || 'info',
 };
@@ -2,7 +2,7 @@ import { PublicKey } from '@solana/web3.js';
 import { BN } from '@coral-xyz/anchor';
 import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress, getAccount, createAssociatedTokenAccountInstruction } from '@solana/spl-token';
 import { Transaction } from '@solana/web3.js';
-import { program, getActiveDcaVaults, DcaVault } from './program';
+import { program, getActiveDcaVaults, getProtocolStatsPDA, getPriceFeedAccount, DcaVault, DCA_SIDE_SELL } from './program';
 import { jupiterService } from './jupiter';
 import { config, connection, getExplorerUrl } from './config';
 import { logger } from './logger';
@@ -87,24 +87,15 @@ export class DcaExecutor {
     const keeperOutputAta = await getAssociatedTokenAddress(vault.outputMint, keeper);
 
     // Ensure keeper has output ATA
-    try {
-      await getAccount(connection, keeperOutputAta);
-    } catch {
-      logger.info('Creating keeper output token account...');
-      const tx = new Transaction().add(
-        createAssociatedTokenAccountInstruction(
-          keeper,
-          keeperOutputAta,
-          keeper,
-          vault.outputMint
-        )
-      );
-      const latestBlockhash = await connection.getLatestBlockhash();
-      tx.recentBlockhash = latestBlockhash.blockhash;
-      tx.feePayer = keeper;
-      tx.sign(config.keeperKeypair);
-      await connection.sendRawTransaction(tx.serialize());
-      await this.sleep(2000);
+    await this.ensureTokenAccount(keeperOutputAta, keeper, vault.outputMint);
+
+    // Sell vaults stream proceeds straight to the owner's output ATA
+    const isSell = vault.side === DCA_SIDE_SELL;
+    const authorityOutputAta = isSell
+      ? await getAssociatedTokenAddress(vault.outputMint, vault.authority)
+      : null;
+    if (authorityOutputAta) {
+      await this.ensureTokenAccount(authorityOutputAta, vault.authority, vault.outputMint);
     }
 
     // Step 1: Execute swap via Jupiter
@@ -143,6 +134,12 @@ export class DcaExecutor {
           vaultOutputToken: vault.outputVault,
           keeperInputToken: keeperInputAta,
           keeperOutputToken: keeperOutputAta,
+          authorityOutputToken: authorityOutputAta,
+          // Price guards and value averaging read the verified Pyth prices
+          // (Sell guards on the input token, Buy guards on the output token)
+          inputPriceUpdate: getPriceFeedAccount(vault.inputFeedId),
+          outputPriceUpdate: getPriceFeedAccount(vault.outputFeedId),
+          protocolStats: getProtocolStatsPDA(vault.inputMint)[0],
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([config.keeperKeypair])
@@ -175,6 +172,25 @@ export class DcaExecutor {
     logger.info(`${'='.repeat(50)}\n`);
   }
 
+  // Create an associated token account (keeper pays) if it does not exist yet
+  private async ensureTokenAccount(ata: PublicKey, owner: PublicKey, mint: PublicKey) {
+    const keeper = config.keeperKeypair.publicKey;
+    try {
+      await getAccount(connection, ata);
+    } catch {
+      logger.info(`Creating token account ${ata.toBase58().slice(0, 8)}...`);
+      const tx = new Transaction().add(
+        createAssociatedTokenAccountInstruction(keeper, ata, owner, mint)
+      );
+      const latestBlockhash = await connection.getLatestBlockhash();
+      tx.recentBlockhash = latestBlockhash.blockhash;
+      tx.feePayer = keeper;
+      tx.sign(config.keeperKeypair);
+      await connection.sendRawTransaction(tx.serialize());
+      await this.sleep(2000);
+    }
+  }
+
   private sleep(ms: number): Promise<void> {
     return new Promise((resolve) => setTimeout(resolve, ms));
   }
@@ -1,8 +1,9 @@
-import { PublicKey } from '@solana/web3.js';
+import { PublicKey, SYSVAR_SLOT_HASHES_PUBKEY } from '@solana/web3.js';
 import { BN } from '@coral-xyz/anchor';
-import { program, getActiveIntentVaults, IntentVault } from './program';
+import { program, getActiveIntentVaults, getProtocolStatsPDA, getPriceFeedAccount, IntentVault } from './program';
 import { getTokenPrice, priceToContractFormat } from './price-oracle';
 import { config, getExplorerUrl } from './config';
+import { loadShieldedReveal, toTriggerReveal, ShieldedReveal } from './shielded-reveals';
 import { logger } from './logger';
 
 export class IntentMonitor {
@@ -60,20 +61,38 @@ export class IntentMonitor {
   }
 
   private async checkIntent(vaultPubkey: PublicKey, vault: IntentVault) {
-    // Get current price of the output token (what user wants to buy/sell)
-    const currentPrice = await getTokenPrice(vault.outputMint.toBase58());
-    if (currentPrice === 0) {
-      logger.warn(`Could not fetch price for ${vault.outputMint.toBase58()}`);
+    // Shielded intents store no trigger; evaluate the owner's reveal instead
+    let reveal: ShieldedReveal | null = null;
+    if (isShielded(vault)) {
+      reveal = loadShieldedReveal(vaultPubkey, vault.triggerCommitment);
+      if (!reveal) {
+        logger.debug(`Intent ${vaultPubkey.toBase58().slice(0, 8)}... is shielded and has no reveal`);
+        return;
+      }
+    }
+
+    // Resolve the price the program compares against the trigger
+    const currentPriceContract = await resolveTriggerPrice(vault);
+    if (currentPriceContract === null) {
+      logger.warn(`Could not fetch prices for intent ${vaultPubkey.toBase58().slice(0, 8)}...`);
       return;
     }
 
-    const currentPriceContract = priceToContractFormat(currentPrice);
-    const triggerPrice = vault.triggerPrice.toNumber();
-    const triggerPriceMax = vault.triggerPriceMax.toNumber();
+    const decimals = triggerDecimals(vault);
+    const currentPrice = currentPriceContract / 10 ** decimals;
+    const triggerPrice = (reveal?.triggerPrice ?? vault.triggerPrice).toNumber();
+    const triggerPriceMax = (reveal?.triggerPriceMax ?? vault.triggerPriceMax).toNumber();
 
     // Check trigger condition
     let shouldTrigger = false;
-    const triggerType = Object.keys(vault.triggerType)[0];
+    const triggerType = reveal?.triggerType ?? Object.keys(vault.triggerType)[0];
+
+    // Trailing stops trigger against the on-chain peak, so raise it first
+    let stopPrice = 0;
+    if (triggerType === 'trailingStop') {
+      const peakPrice = await this.updateTrailingPeak(vaultPubkey, vault, currentPriceContract);
+      stopPrice = trailingStopPrice(peakPrice, vault.triggerType.trailingStop.trailBps);
+    }
 
     switch (triggerType) {
       case 'priceAbove':
@@ -85,35 +104,81 @@ export class IntentMonitor {
       case 'priceRange':
         shouldTrigger = currentPriceContract >= triggerPrice && currentPriceContract <= triggerPriceMax;
         break;
+      case 'trailingStop':
+        // Mirror IntentVault::check_trigger: no peak, no trigger
+        shouldTrigger = stopPrice > 0 && currentPriceContract <= stopPrice;
+        break;
     }
 
+    const shownTrigger = (triggerType === 'trailingStop' ? stopPrice : triggerPrice) / 10 ** decimals;
     if (!shouldTrigger) {
       logger.debug(
-        `Intent ${vaultPubkey.toBase58().slice(0, 8)}... - Price: $${currentPrice.toFixed(4)}, ` +
-        `Trigger: ${triggerType} $${(triggerPrice / 1_000_000).toFixed(4)}`
+        `Intent ${vaultPubkey.toBase58().slice(0, 8)}... - Price: ${currentPrice.toFixed(4)}, ` +
+        `Trigger: ${triggerType} ${shownTrigger.toFixed(4)}`
       );
       return;
     }
 
     logger.info(`🎯 Intent triggered! ${vaultPubkey.toBase58().slice(0, 8)}...`);
-    logger.info(`   Current price: $${currentPrice.toFixed(4)}, Trigger: $${(triggerPrice / 1_000_000).toFixed(4)}`);
+    logger.info(`   Current price: ${currentPrice.toFixed(4)}, Trigger: ${shownTrigger.toFixed(4)}`);
 
     // Execute the intent
-    await this.executeIntent(vaultPubkey, vault, currentPriceContract);
+    await this.executeIntent(vaultPubkey, vault, currentPriceContract, reveal);
   }
 
-  private async executeIntent(
+  // Crank update_trailing_intent when the price made a new peak, returning
+  // the peak the trigger is checked against
+  private async updateTrailingPeak(
     vaultPubkey: PublicKey,
     vault: IntentVault,
     currentPrice: number
+  ): Promise<number> {
+    const peakPrice = vault.peakPrice.toNumber();
+    if (currentPrice <= peakPrice) {
+      return peakPrice;
+    }
+
+    try {
+      const tx = await program.methods
+        .updateTrailingIntent()
+        .accounts({
+          intentVault: vaultPubkey,
+          inputPriceUpdate: getPriceFeedAccount(vault.inputFeedId),
+          outputPriceUpdate: getPriceFeedAccount(vault.outputFeedId),
+        })
+        .rpc();
+      logger.debug(`Trailing peak of ${vaultPubkey.toBase58().slice(0, 8)}... updated: ${tx}`);
+    } catch (error: any) {
+      logger.warn(`Failed to update trailing peak of ${vaultPubkey.toBase58().slice(0, 8)}...: ${error.message}`);
+    }
+
+    // The oracle price may differ from ours, so use the peak the program stored
+    const updated = await (program.account as any).intentVault.fetch(vaultPubkey);
+    return updated.peakPrice.toNumber();
+  }
+
+  private async executeIntent(
+    vaultPubkey: PublicKey,
+    vault: IntentVault,
+    currentPrice: number,
+    reveal: ShieldedReveal | null
   ) {
     try {
       const tx = await program.methods
-        .executeIntent({ currentPrice: new BN(currentPrice) })
+        .executeIntent({
+          currentPrice: new BN(currentPrice),
+          // Only sent once the trigger is met: the params are public from here on
+          triggerReveal: reveal ? toTriggerReveal(reveal) : null,
+        })
         .accounts({
           keeper: config.keeperKeypair.publicKey,
           intentVault: vaultPubkey,
           vaultInputToken: vault.inputVault,
+          protocolStats: getProtocolStatsPDA(vault.inputMint)[0],
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+          // Oracle-bound intents are checked against the verified Pyth price
+          inputPriceUpdate: getPriceFeedAccount(vault.inputFeedId),
+          outputPriceUpdate: getPriceFeedAccount(vault.outputFeedId),
         })
         .signers([config.keeperKeypair])
         .rpc();
@@ -137,4 +202,51 @@ export class IntentMonitor {
   }
 }
 
+function isShielded(vault: IntentVault): boolean {
+  return (vault.triggerCommitment ?? []).some((byte) => byte !== 0);
+}
+
+// Mirror IntentVault::trailing_stop_price: trail_bps below the peak
+function trailingStopPrice(peakPrice: number, trailBps: number): number {
+  return Math.floor((peakPrice * (10_000 - trailBps)) / 10_000);
+}
+
+// Swap intents created before layout v2 were priced at a fixed 6 decimals
+const SWAP_RATE_DECIMALS_VERSION = 2;
+
+function usesCrossRate(vault: IntentVault): boolean {
+  return 'crossRate' in (vault.triggerMode ?? {}) || 'swap' in vault.intentType;
+}
+
+// Decimals of the price the trigger compares against (IntentVault::trigger_rate_decimals)
+function triggerDecimals(vault: IntentVault): number {
+  if (!usesCrossRate(vault)) {
+    return 6;
+  }
+  const legacySwap =
+    'swap' in vault.intentType &&
+    !('crossRate' in (vault.triggerMode ?? {})) &&
+    (vault.version ?? 0) < SWAP_RATE_DECIMALS_VERSION;
+  return legacySwap ? 6 : vault.rateDecimals ?? 0;
+}
+
+// Mirror IntentVault::resolve_trigger_price: Buy watches the output token's
+// USD price, Sell the input token's, and Swap / CrossRate the input token
+// priced in output tokens with the vault's rate decimals
+async function resolveTriggerPrice(vault: IntentVault): Promise<number | null> {
+  const watchesInput = usesCrossRate(vault) || 'sell' in vault.intentType;
+  const watchesOutput = usesCrossRate(vault) || 'buy' in vault.intentType;
+
+  const inputPrice = watchesInput ? await getTokenPrice(vault.inputMint.toBase58()) : 0;
+  const outputPrice = watchesOutput ? await getTokenPrice(vault.outputMint.toBase58()) : 0;
+  if ((watchesInput && inputPrice === 0) || (watchesOutput && outputPrice === 0)) {
+    return null;
+  }
+
+  if (usesCrossRate(vault)) {
+    return Math.floor((inputPrice / outputPrice) * 10 ** triggerDecimals(vault));
+  }
+  return priceToContractFormat(watchesInput ? inputPrice : outputPrice);
+}
+
 export const intentMonitor = new IntentMonitor();
@@ -1,5 +1,5 @@
-import { Program, AnchorProvider, Idl, BN, Wallet } from '@coral-xyz/anchor';
-import { PublicKey, Keypair, Transaction, VersionedTransaction } from '@solana/web3.js';
+import { Program, AnchorProvider, Idl, BN, Wallet, utils } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, Transaction, VersionedTransaction, GetProgramAccountsFilter } from '@solana/web3.js';
 import { config, connection } from './config';
 import { logger } from './logger';
 import * as fs from 'fs';
@@ -64,8 +64,11 @@ export interface DcaVault {
   executionCount: number;
   lastExecution: BN;
   nextExecution: BN;
-  isActive: boolean;
+  isActive: number; // 0/1, stored as u8 in the zero-copy layout
   createdAt: BN;
+  outputFeedId: number[];
+  inputFeedId: number[];
+  side: number; // 0 = Buy, 1 = Sell (DcaSide as u8)
   bump: number;
   inputVaultBump: number;
   outputVaultBump: number;
@@ -83,9 +86,11 @@ export interface IntentVault {
   triggerType: any;
   triggerPrice: BN;
   triggerPriceMax: BN;
+  peakPrice: BN;
   executionStyle: any;
   numChunks: number;
   chunksExecuted: number;
+  nextChunkAmount: BN;
   expiresAt: BN;
   triggeredAt: BN;
   executedAt: BN;
@@ -95,12 +100,29 @@ export interface IntentVault {
   totalReceived: BN;
   bump: number;
   vaultBump: number;
+  outputFeedId: number[];
+  inputFeedId: number[];
+  triggerMode: any;
+  rateDecimals: number;
+  triggerCommitment: number[];
+  version: number;
 }
 
-// Fetch all DCA vaults
-export async function getAllDcaVaults(): Promise<{ publicKey: PublicKey; account: DcaVault }[]> {
+// DcaVault::side of vaults that exit the input token, streaming proceeds to the owner
+export const DCA_SIDE_SELL = 1;
+
+// Zero-copy DcaVault layout (DcaVault::SPACE, IS_ACTIVE_OFFSET and
+// NEXT_EXECUTION_OFFSET, checked by the program's layout tests)
+export const DCA_VAULT_SPACE = 416;
+export const DCA_VAULT_IS_ACTIVE_OFFSET = 340;
+export const DCA_VAULT_NEXT_EXECUTION_OFFSET = 208;
+
+// Fetch all DCA vaults (optionally narrowed by RPC filters)
+export async function getAllDcaVaults(
+  filters: GetProgramAccountsFilter[] = []
+): Promise<{ publicKey: PublicKey; account: DcaVault }[]> {
   try {
-    const accounts = await (program.account as any).dcaVault.all();
+    const accounts = await (program.account as any).dcaVault.all(filters);
     return accounts.map((a: any) => ({
       publicKey: a.publicKey,
       account: a.account as DcaVault,
@@ -113,7 +135,12 @@ export async function getAllDcaVaults(): Promise<{ publicKey: PublicKey; account
 
 // Fetch active DCA vaults (ready to execute)
 export async function getActiveDcaVaults(): Promise<{ publicKey: PublicKey; account: DcaVault }[]> {
-  const allVaults = await getAllDcaVaults();
+  // Let the RPC node drop inactive (and not yet migrated) vaults before they
+  // are downloaded
+  const allVaults = await getAllDcaVaults([
+    { dataSize: DCA_VAULT_SPACE },
+    { memcmp: { offset: DCA_VAULT_IS_ACTIVE_OFFSET, bytes: utils.bytes.bs58.encode(Buffer.from([1])) } },
+  ]);
   const now = Math.floor(Date.now() / 1000);
 
   return allVaults.filter((v) => {
@@ -182,4 +209,50 @@ export function getOutputVaultPDA(dcaVault: PublicKey): [PublicKey, number] {
     [Buffer.from('output_vault'), dcaVault.toBuffer()],
     config.programId
   );
-}
\ No newline at end of file
+}
+// Get PDA for a user's vault registry
+export function getUserRegistryPDA(authority: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('user_registry'), authority.toBuffer()],
+    config.programId
+  );
+}
+
+// User registry type
+export interface UserRegistry {
+  authority: PublicKey;
+  nextIntentNonce: BN;
+  dcasCreated: number;
+  intentsCreated: number;
+  bump: number;
+  dcaVaults: PublicKey[];
+  intentVaults: PublicKey[];
+}
+
+// Fetch a user's open vaults in one account read (null if they have none registered)
+export async function getUserRegistry(authority: PublicKey): Promise<UserRegistry | null> {
+  const [registry] = getUserRegistryPDA(authority);
+  return (await (program.account as any).userRegistry.fetchNullable(registry)) as UserRegistry | null;
+}
+
+// Pyth push oracle: sponsored PriceUpdateV2 accounts, one per feed id and shard
+export const PYTH_PUSH_ORACLE_ID = new PublicKey('pythWSnswVUd12oZpeFP8e9CVaEqJg25g1Vtc2biRsT');
+const PYTH_SHARD_ID = 0;
+
+// Get the sponsored price update account of a Pyth feed (null for an unset feed)
+export function getPriceFeedAccount(feedId: number[]): PublicKey | null {
+  if (!feedId || feedId.every((byte) => byte === 0)) {
+    return null;
+  }
+  const shard = Buffer.alloc(2);
+  shard.writeUInt16LE(PYTH_SHARD_ID);
+  return PublicKey.findProgramAddressSync([shard, Buffer.from(feedId)], PYTH_PUSH_ORACLE_ID)[0];
+}
+
+// Get PDA for a mint's protocol stats shard
+export function getProtocolStatsPDA(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('protocol_stats'), mint.toBuffer()],
+    config.programId
+  );
+}
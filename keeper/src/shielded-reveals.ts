@@ -0,0 +1,97 @@
+import { PublicKey } from '@solana/web3.js';
+import { BN } from '@coral-xyz/anchor';
+import { createHash } from 'crypto';
+import * as fs from 'fs';
+import { config } from './config';
+import { logger } from './logger';
+
+// Trigger params an owner hands to the keeper for one shielded intent.
+//
+// The reveals file (SHIELDED_REVEALS_PATH) maps intent vault addresses to
+// the params and salt the owner committed to at creation:
+//
+//   {
+//     "<intent vault>": {
+//       "triggerType": "priceBelow",
+//       "triggerPrice": "180000000",
+//       "triggerPriceMax": "0",
+//       "salt": "<32 bytes, hex>"
+//     }
+//   }
+//
+// The owner sends their entry privately to the keeper operator, who adds it
+// to the file. The file is re-read on every check, so no restart is needed.
+// The keeper only submits the reveal once the trigger is met, and the params
+// become public with that transaction.
+export interface ShieldedReveal {
+  triggerType: 'priceAbove' | 'priceBelow' | 'priceRange';
+  triggerPrice: BN;
+  triggerPriceMax: BN;
+  salt: Buffer;
+}
+
+// Trigger type codes hashed into the commitment (TriggerReveal::commitment)
+const TRIGGER_TYPE_CODES = { priceAbove: 0, priceBelow: 1, priceRange: 2 };
+
+// Mirror TriggerReveal::commitment:
+// sha256(trigger_type u8 || trigger_price u64 LE || trigger_price_max u64 LE || salt)
+export function triggerCommitment(reveal: ShieldedReveal): Buffer {
+  return createHash('sha256')
+    .update(Buffer.from([TRIGGER_TYPE_CODES[reveal.triggerType]]))
+    .update(reveal.triggerPrice.toArrayLike(Buffer, 'le', 8))
+    .update(reveal.triggerPriceMax.toArrayLike(Buffer, 'le', 8))
+    .update(reveal.salt)
+    .digest();
+}
+
+function parseReveal(entry: any): ShieldedReveal | null {
+  if (!entry || !(entry.triggerType in TRIGGER_TYPE_CODES)) {
+    return null;
+  }
+  const salt = Buffer.from(String(entry.salt ?? ''), 'hex');
+  if (salt.length !== 32) {
+    return null;
+  }
+  return {
+    triggerType: entry.triggerType,
+    triggerPrice: new BN(String(entry.triggerPrice ?? '0')),
+    triggerPriceMax: new BN(String(entry.triggerPriceMax ?? '0')),
+    salt,
+  };
+}
+
+// Find the owner's reveal for a shielded intent, accepting it only if it
+// opens the commitment stored on the vault
+export function loadShieldedReveal(vault: PublicKey, commitment: number[]): ShieldedReveal | null {
+  if (!config.shieldedRevealsPath || !fs.existsSync(config.shieldedRevealsPath)) {
+    return null;
+  }
+
+  let reveals: Record<string, unknown>;
+  try {
+    reveals = JSON.parse(fs.readFileSync(config.shieldedRevealsPath, 'utf-8'));
+  } catch (error) {
+    logger.error('Failed to read shielded reveals file:', error);
+    return null;
+  }
+
+  const reveal = parseReveal(reveals[vault.toBase58()]);
+  if (!reveal) {
+    return null;
+  }
+  if (!triggerCommitment(reveal).equals(Buffer.from(commitment))) {
+    logger.warn(`Reveal for intent ${vault.toBase58().slice(0, 8)}... does not match its commitment`);
+    return null;
+  }
+  return reveal;
+}
+
+// Encode a reveal as the instruction's TriggerReveal
+export function toTriggerReveal(reveal: ShieldedReveal) {
+  return {
+    triggerType: { [reveal.triggerType]: {} },
+    triggerPrice: reveal.triggerPrice,
+    triggerPriceMax: reveal.triggerPriceMax,
+    salt: Array.from(reveal.salt),
+  };
+}
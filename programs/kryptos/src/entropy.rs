@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::KryptosError;
+
+/// SlotHashes sysvar, whose newest entry seeds on-chain randomness
+pub use solana_sdk_ids::sysvar::slot_hashes::ID as SLOT_HASHES_ID;
+
+/// Size of the SlotHashes header (entry count) plus one `(slot, hash)` entry
+const NEWEST_ENTRY_END: usize = 8 + 8 + 32;
+
+/// Hash of the most recent slot, read from the raw SlotHashes sysvar
+///
+/// The sysvar is too large to deserialize on-chain, so only the newest entry
+/// is read. It is unknown until that slot is produced, so values derived from
+/// it cannot be precomputed when a vault is created or scheduled.
+pub fn recent_slot_hash(slot_hashes: &AccountInfo) -> Result<[u8; 32]> {
+    require_keys_eq!(slot_hashes.key(), SLOT_HASHES_ID, KryptosError::InvalidSlotHashes);
+
+    let data = slot_hashes.try_borrow_data()?;
+    require!(data.len() >= NEWEST_ENTRY_END, KryptosError::InvalidSlotHashes);
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&data[16..NEWEST_ENTRY_END]);
+    Ok(hash)
+}
+
+/// Per-vault seed: the slot hash mixed with the vault address and a counter,
+/// so vaults scheduled in the same slot draw independent values
+pub fn vault_seed(slot_hash: &[u8; 32], vault: &Pubkey, counter: u64) -> [u8; 32] {
+    solana_sha256_hasher::hashv(&[slot_hash, vault.as_ref(), &counter.to_le_bytes()]).to_bytes()
+}
+
+/// `u64` drawn from bytes `8 * index..8 * index + 8` of a seed (index 0-3)
+pub fn seed_u64(seed: &[u8; 32], index: usize) -> u64 {
+    let start = index * 8;
+    u64::from_le_bytes(seed[start..start + 8].try_into().unwrap())
+}
+
+/// Value drawn uniformly-ish from `center - spread..=center + spread`
+pub fn jitter(center: u64, spread: u64, draw: u64) -> u64 {
+    let span = spread as u128 * 2 + 1;
+    let offset = (draw as u128 % span) as u64;
+    center.saturating_sub(spread).saturating_add(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jitter_stays_within_spread() {
+        assert_eq!(jitter(100, 0, 12345), 100);
+        assert_eq!(jitter(100, 10, 0), 90);
+        assert_eq!(jitter(100, 10, 20), 110);
+        assert_eq!(jitter(100, 10, 21), 90);
+        for draw in 0..100 {
+            let value = jitter(1_000, 250, draw * 7919);
+            assert!((750..=1_250).contains(&value));
+        }
+
+        // Seeds differ per vault and per counter
+        let slot_hash = [7u8; 32];
+        let vault = Pubkey::new_from_array([1; 32]);
+        assert_ne!(vault_seed(&slot_hash, &vault, 0), vault_seed(&slot_hash, &vault, 1));
+        assert_ne!(
+            vault_seed(&slot_hash, &vault, 0),
+            vault_seed(&slot_hash, &Pubkey::new_from_array([2; 32]), 0)
+        );
+    }
+}
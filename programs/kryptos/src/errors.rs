@@ -37,6 +37,42 @@ pub enum KryptosError {
     #[msg("DCA vault still has remaining funds")]
     DcaHasRemainingFunds,
 
+    #[msg("Invalid trade limits (min must not exceed max)")]
+    InvalidTradeLimits,
+
+    #[msg("Strategy not supported for this DCA side")]
+    UnsupportedDcaStrategy,
+
+    #[msg("Invalid basket outputs (1-5 distinct mints, weights summing to 10000 bps)")]
+    InvalidBasketOutputs,
+
+    #[msg("Basket output token account not opened")]
+    BasketOutputNotOpen,
+
+    #[msg("Basket output token account already opened")]
+    BasketOutputAlreadyOpen,
+
+    #[msg("Invalid DCA batch (vault groups must match amounts and share one mint pair)")]
+    InvalidBatch,
+
+    #[msg("DCA vault needs oracle prices or streams proceeds and cannot be batched")]
+    UnsupportedBatchVault,
+
+    #[msg("Invalid slippage (max 1000 bps)")]
+    InvalidSlippage,
+
+    #[msg("DCA vaults cannot be matched (opposing pairs, shared feeds and matching enabled required)")]
+    UnmatchableVaults,
+
+    #[msg("Invalid DCA pool config (cadence min 3600s, spend 1-5000 bps)")]
+    InvalidPoolConfig,
+
+    #[msg("Insufficient pool shares")]
+    InsufficientShares,
+
+    #[msg("Pool epoch record required (draining sweep or stale position)")]
+    PoolEpochRequired,
+
     // === Intent Errors ===
     #[msg("Intent has expired")]
     IntentExpired,
@@ -65,6 +101,85 @@ pub enum KryptosError {
     #[msg("Intent vault still has remaining funds")]
     IntentHasRemainingFunds,
 
+    #[msg("Invalid trailing distance (must be between 1 and 9999 bps)")]
+    InvalidTrailBps,
+
+    #[msg("Intent is not a trailing stop")]
+    NotTrailingIntent,
+
+    #[msg("Invalid rate decimals (1-18 for CrossRate and Swap triggers)")]
+    InvalidRateDecimals,
+
+    #[msg("Invalid chunk configuration for execution style")]
+    InvalidChunkConfig,
+
+    #[msg("Chunk amount does not match execution style")]
+    InvalidChunkAmount,
+
+    #[msg("Next chunk execution not yet allowed")]
+    ChunkNotReady,
+
+    #[msg("Shielded intents must send an empty cleartext trigger (PriceAbove, zero prices)")]
+    InvalidShieldedTrigger,
+
+    #[msg("Revealed trigger does not match the intent's commitment")]
+    TriggerRevealMismatch,
+
+    // === Rebalance Errors ===
+    #[msg("Invalid rebalance assets (2-5 distinct priced mints, weights summing to 10000 bps)")]
+    InvalidRebalanceAssets,
+
+    #[msg("Invalid rebalance config (drift threshold 1-5000 bps, slippage max 1000 bps)")]
+    InvalidRebalanceConfig,
+
+    #[msg("Rebalance vault is not active")]
+    RebalanceNotActive,
+
+    #[msg("Rebalance asset token account not opened")]
+    RebalanceAssetNotOpen,
+
+    #[msg("Rebalance asset token account already opened")]
+    RebalanceAssetAlreadyOpen,
+
+    #[msg("Portfolio drift below rebalance threshold")]
+    DriftBelowThreshold,
+
+    #[msg("Rebalance trade must move value from an overweight to an underweight asset")]
+    InvalidRebalanceTrade,
+
+    #[msg("Rebalance vault still has remaining funds")]
+    RebalanceHasRemainingFunds,
+
+    // === Stream Errors ===
+    #[msg("Invalid stream configuration")]
+    InvalidStreamConfig,
+
+    #[msg("Stream is not active")]
+    StreamNotActive,
+
+    #[msg("Nothing vested to claim yet")]
+    NothingToClaim,
+
+    // === Metadata Errors ===
+    #[msg("Invalid metadata size or write range (max 4096 bytes)")]
+    InvalidMetadata,
+
+    #[msg("Metadata can only be attached to a DCA or intent vault")]
+    InvalidMetadataVault,
+
+    // === Drop Errors ===
+    #[msg("Invalid drop lock")]
+    InvalidDropLock,
+
+    #[msg("Drop claim window has expired")]
+    DropExpired,
+
+    #[msg("Drop claim window has not expired yet")]
+    DropNotExpired,
+
+    #[msg("Missing or invalid claim key signature")]
+    InvalidClaimSignature,
+
     // === Swap Errors ===
     #[msg("Swap failed")]
     SwapFailed,
@@ -82,7 +197,37 @@ pub enum KryptosError {
     #[msg("Token account mismatch")]
     TokenAccountMismatch,
 
+    // === Oracle Errors ===
+    #[msg("Invalid oracle price account")]
+    InvalidOracle,
+
+    #[msg("Oracle price feed does not match the vault")]
+    OracleFeedMismatch,
+
+    #[msg("Oracle price is stale")]
+    StalePrice,
+
+    #[msg("Oracle price confidence interval too wide")]
+    PriceTooUncertain,
+
+    #[msg("Oracle price account required")]
+    OracleRequired,
+
+    #[msg("Invalid SlotHashes sysvar account")]
+    InvalidSlotHashes,
+
+    // === Migration Errors ===
+    #[msg("Account is not a vault of the expected type")]
+    InvalidMigrationAccount,
+
+    #[msg("Vault already uses the current layout")]
+    VaultAlreadyMigrated,
+
     // === Keeper Errors ===
     #[msg("Invalid keeper authority")]
     InvalidKeeper,
+
+    // === Stats Errors ===
+    #[msg("Stats shard of the vault's input mint required")]
+    StatsShardRequired,
 }
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::DcaSkipReason;
+
 // === DCA Events ===
 
 #[event]
@@ -23,6 +25,37 @@ pub struct DcaExecuted {
     pub executed_at: i64,
 }
 
+#[event]
+pub struct DcaBatchExecuted {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub vault_count: u32,
+    pub total_spent: u64,
+    pub total_received: u64,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct DcaMatched {
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub matched_a: u64,
+    pub matched_b: u64,
+    pub external_spent: u64,
+    pub external_received: u64,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct DcaSkipped {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub reason: DcaSkipReason,
+    pub current_price: u64,
+    pub next_execution: i64,
+    pub skipped_at: i64,
+}
+
 #[event]
 pub struct DcaCancelled {
     pub vault: Pubkey,
@@ -31,6 +64,15 @@ pub struct DcaCancelled {
     pub cancelled_at: i64,
 }
 
+#[event]
+pub struct DcaOutputClaimed {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+}
+
 #[event]
 pub struct DcaCompleted {
     pub vault: Pubkey,
@@ -41,6 +83,98 @@ pub struct DcaCompleted {
     pub completed_at: i64,
 }
 
+// === DCA Pool Events ===
+
+#[event]
+pub struct DcaPoolCreated {
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub cadence_seconds: u32,
+    pub spend_bps: u16,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct DcaPoolDeposited {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub shares: u128,
+    pub deposited_at: i64,
+}
+
+#[event]
+pub struct DcaPoolWithdrawn {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub shares: u128,
+    pub withdrawn_at: i64,
+}
+
+#[event]
+pub struct DcaPoolExecuted {
+    pub pool: Pubkey,
+    pub amount_spent: u64,
+    pub amount_received: u64,
+    pub total_shares: u128,
+    pub next_execution: i64,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct DcaPoolClaimed {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+}
+
+// === Basket DCA Events ===
+
+#[event]
+pub struct BasketDcaCreated {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mints: Vec<Pubkey>,
+    pub weights_bps: Vec<u16>,
+    pub total_amount: u64,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct BasketDcaExecuted {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub output_index: u8,
+    pub output_mint: Pubkey,
+    pub amount_spent: u64,
+    pub amount_received: u64,
+    pub execution_count: u32,
+    pub next_execution: i64,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct BasketDcaOutputClaimed {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+}
+
+#[event]
+pub struct BasketDcaCompleted {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub total_spent: u64,
+    pub execution_count: u32,
+    pub completed_at: i64,
+}
+
 // === Intent Events ===
 
 #[event]
@@ -51,7 +185,10 @@ pub struct IntentCreated {
     pub input_mint: Pubkey,
     pub output_mint: Pubkey,
     pub amount: u64,
+    /// Zero for shielded intents
     pub trigger_price: u64,
+    /// Hash of the hidden trigger parameters (zero = cleartext trigger)
+    pub trigger_commitment: [u8; 32],
     pub expires_at: i64,
     pub created_at: i64,
 }
@@ -65,6 +202,16 @@ pub struct IntentTriggered {
     pub triggered_at: i64,
 }
 
+#[event]
+pub struct TrailingPeakUpdated {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub previous_peak: u64,
+    pub new_peak: u64,
+    pub stop_price: u64,
+    pub updated_at: i64,
+}
+
 #[event]
 pub struct IntentExecuted {
     pub vault: Pubkey,
@@ -89,6 +236,138 @@ pub struct IntentExpired {
     pub expired_at: i64,
 }
 
+// === Rebalance Events ===
+
+#[event]
+pub struct RebalanceVaultCreated {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub mints: Vec<Pubkey>,
+    pub target_weights_bps: Vec<u16>,
+    pub drift_threshold_bps: u16,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct RebalanceDeposited {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub deposited_at: i64,
+}
+
+#[event]
+pub struct PortfolioRebalanced {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub sell_mint: Pubkey,
+    pub buy_mint: Pubkey,
+    pub amount_sold: u64,
+    pub amount_bought: u64,
+    pub value_sold: u64,
+    pub value_bought: u64,
+    pub max_drift_bps: u16,
+    pub rebalanced_at: i64,
+}
+
+// === Stream Events ===
+
+#[event]
+pub struct StreamCreated {
+    pub stream: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub amount_per_payment: u64,
+    pub interval_seconds: u32,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct StreamClaimed {
+    pub stream: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub claimed_at: i64,
+}
+
+#[event]
+pub struct StreamCancelled {
+    pub stream: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub vested_paid: u64,
+    pub unvested_refunded: u64,
+    pub cancelled_at: i64,
+}
+
+// === Drop Events ===
+
+#[event]
+pub struct DropCreated {
+    pub drop: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct DropClaimed {
+    pub drop: Pubkey,
+    pub creator: Pubkey,
+    pub claimant: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+}
+
+#[event]
+pub struct DropRefunded {
+    pub drop: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub refunded_at: i64,
+}
+
+// === Burn Events ===
+
+#[event]
+pub struct TokensBurned {
+    pub receipt: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_burned: u64,
+    pub burn_count: u32,
+    pub burned_at: i64,
+}
+
+// === Metadata Events ===
+
+#[event]
+pub struct VaultMetadataUpdated {
+    pub metadata: Pubkey,
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub len: u32,
+    pub updated_at: i64,
+}
+
+// === Migration Events ===
+
+#[event]
+pub struct VaultMigrated {
+    pub vault: Pubkey,
+    pub version: u8,
+    pub space: u32,
+    pub migrated_at: i64,
+}
+
 // === Withdrawal Events ===
 
 #[event]
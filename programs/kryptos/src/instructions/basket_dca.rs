@@ -0,0 +1,721 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer, CloseAccount, close_account};
+
+use crate::state::{BasketDcaVault, BasketOutput, ProtocolStats, MAX_BASKET_OUTPUTS};
+use crate::errors::KryptosError;
+use crate::events::{BasketDcaCreated, BasketDcaExecuted, BasketDcaCompleted, BasketDcaOutputClaimed, DcaCancelled, FundsWithdrawn};
+
+// ============================================
+// INITIALIZE BASKET DCA
+// ============================================
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct BasketOutputParams {
+    /// Token to buy
+    pub mint: Pubkey,
+    /// Share of the deposit allocated to this token (basis points)
+    pub weight_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeBasketDcaParams {
+    /// Unique nonce to allow multiple baskets (user-provided)
+    pub nonce: u64,
+    /// Total amount to DCA (in smallest unit)
+    pub total_amount: u64,
+    /// Amount per execution (base amount)
+    pub amount_per_trade: u64,
+    /// Variance in basis points (e.g., 2000 = 20%, max 5000 = 50%)
+    pub variance_bps: u16,
+    /// Minimum executions per week
+    pub min_executions: u8,
+    /// Maximum executions per week
+    pub max_executions: u8,
+    /// Execution window start hour (UTC, 0-23)
+    pub window_start_hour: u8,
+    /// Execution window end hour (UTC, 0-23)
+    pub window_end_hour: u8,
+    /// Output tokens and weights (1-5 distinct mints, weights summing to 10000)
+    pub outputs: Vec<BasketOutputParams>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: InitializeBasketDcaParams)]
+pub struct InitializeBasketDca<'info> {
+    /// User creating the basket
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Basket DCA vault PDA (using nonce for uniqueness)
+    #[account(
+        init,
+        payer = authority,
+        space = BasketDcaVault::SPACE,
+        seeds = [
+            BasketDcaVault::SEED_PREFIX,
+            authority.key().as_ref(),
+            input_mint.key().as_ref(),
+            &params.nonce.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub basket_dca_vault: Account<'info, BasketDcaVault>,
+
+    /// Input token mint (token to spend)
+    pub input_mint: Account<'info, Mint>,
+
+    /// User's input token account (source of funds)
+    #[account(
+        mut,
+        constraint = user_input_token.mint == input_mint.key() @ KryptosError::InvalidMint,
+        constraint = user_input_token.owner == authority.key() @ KryptosError::Unauthorized,
+    )]
+    pub user_input_token: Account<'info, TokenAccount>,
+
+    /// Vault's input token account (holds deposited funds)
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            b"basket_input_vault",
+            basket_dca_vault.key().as_ref(),
+        ],
+        bump,
+        token::mint = input_mint,
+        token::authority = basket_dca_vault,
+    )]
+    pub vault_input_token: Account<'info, TokenAccount>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler_initialize_basket_dca(
+    ctx: Context<InitializeBasketDca>,
+    params: InitializeBasketDcaParams,
+) -> Result<()> {
+    // Validate parameters
+    require!(params.total_amount > 0, KryptosError::InvalidAmount);
+    require!(params.amount_per_trade > 0, KryptosError::InvalidAmount);
+    require!(params.variance_bps <= 5000, KryptosError::InvalidVariance);
+    require!(
+        params.min_executions > 0 && params.max_executions >= params.min_executions,
+        KryptosError::InvalidExecutionRange
+    );
+    require!(
+        params.window_start_hour < 24 && params.window_end_hour < 24,
+        KryptosError::InvalidTimeWindow
+    );
+
+    // Validate outputs: distinct non-input mints with weights summing to 100%
+    let input_mint = ctx.accounts.input_mint.key();
+    require!(
+        !params.outputs.is_empty() && params.outputs.len() <= MAX_BASKET_OUTPUTS,
+        KryptosError::InvalidBasketOutputs
+    );
+    let mut total_weight = 0u32;
+    for (index, output) in params.outputs.iter().enumerate() {
+        require!(
+            output.weight_bps > 0
+                && output.mint != input_mint
+                && params.outputs[..index].iter().all(|other| other.mint != output.mint),
+            KryptosError::InvalidBasketOutputs
+        );
+        total_weight += output.weight_bps as u32;
+    }
+    require!(total_weight == 10_000, KryptosError::InvalidBasketOutputs);
+
+    // Get current timestamp
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Initialize basket vault; output token accounts are opened separately
+    let basket = &mut ctx.accounts.basket_dca_vault;
+    basket.authority = ctx.accounts.authority.key();
+    basket.nonce = params.nonce;
+    basket.input_mint = input_mint;
+    basket.input_vault = ctx.accounts.vault_input_token.key();
+    basket.num_outputs = params.outputs.len() as u8;
+    for (slot, output) in basket.outputs.iter_mut().zip(params.outputs.iter()) {
+        *slot = BasketOutput {
+            mint: output.mint,
+            weight_bps: output.weight_bps,
+            ..BasketOutput::default()
+        };
+    }
+    basket.total_amount = params.total_amount;
+    basket.amount_per_trade = params.amount_per_trade;
+    basket.variance_bps = params.variance_bps;
+    basket.min_executions = params.min_executions;
+    basket.max_executions = params.max_executions;
+    basket.window_start_hour = params.window_start_hour;
+    basket.window_end_hour = params.window_end_hour;
+    basket.total_spent = 0;
+    basket.execution_count = 0;
+    basket.last_execution = 0;
+    basket.next_execution = current_time + 3600; // 1 hour from now
+    basket.is_active = true;
+    basket.created_at = current_time;
+    basket.bump = ctx.bumps.basket_dca_vault;
+    basket.input_vault_bump = ctx.bumps.vault_input_token;
+
+    // Transfer tokens from user to vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_input_token.to_account_info(),
+            to: ctx.accounts.vault_input_token.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, params.total_amount)?;
+
+    // Emit event
+    emit!(BasketDcaCreated {
+        vault: basket.key(),
+        authority: basket.authority,
+        input_mint: basket.input_mint,
+        output_mints: params.outputs.iter().map(|output| output.mint).collect(),
+        weights_bps: params.outputs.iter().map(|output| output.weight_bps).collect(),
+        total_amount: basket.total_amount,
+        created_at: current_time,
+    });
+
+    msg!("Basket DCA vault created successfully");
+    msg!("Vault: {}", basket.key());
+    msg!("Outputs: {} | Total amount: {}", basket.num_outputs, params.total_amount);
+
+    Ok(())
+}
+
+// ============================================
+// OPEN BASKET OUTPUT
+// ============================================
+
+#[derive(Accounts)]
+#[instruction(output_index: u8)]
+pub struct OpenBasketOutput<'info> {
+    /// Owner of the basket (pays rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Basket the output belongs to
+    #[account(
+        mut,
+        constraint = basket_dca_vault.authority == authority.key() @ KryptosError::Unauthorized,
+        constraint = output_index < basket_dca_vault.num_outputs @ KryptosError::InvalidBasketOutputs,
+        constraint = !basket_dca_vault.outputs[output_index as usize].is_open()
+            @ KryptosError::BasketOutputAlreadyOpen,
+    )]
+    pub basket_dca_vault: Account<'info, BasketDcaVault>,
+
+    /// Output token mint configured at this index
+    #[account(
+        constraint = output_mint.key() == basket_dca_vault.outputs[output_index as usize].mint
+            @ KryptosError::InvalidMint,
+    )]
+    pub output_mint: Account<'info, Mint>,
+
+    /// Vault's output token account for this index
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            BasketDcaVault::OUTPUT_SEED_PREFIX,
+            basket_dca_vault.key().as_ref(),
+            &[output_index],
+        ],
+        bump,
+        token::mint = output_mint,
+        token::authority = basket_dca_vault,
+    )]
+    pub vault_output_token: Account<'info, TokenAccount>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler_open_basket_output(ctx: Context<OpenBasketOutput>, output_index: u8) -> Result<()> {
+    let basket = &mut ctx.accounts.basket_dca_vault;
+    let output = &mut basket.outputs[output_index as usize];
+    output.vault = ctx.accounts.vault_output_token.key();
+    output.vault_bump = ctx.bumps.vault_output_token;
+
+    msg!("Basket output {} opened: {}", output_index, output.vault);
+
+    Ok(())
+}
+
+// ============================================
+// EXECUTE BASKET DCA
+// ============================================
+
+#[derive(Accounts)]
+pub struct ExecuteBasketDca<'info> {
+    /// Keeper/crank that triggers execution
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Basket vault to execute
+    #[account(
+        mut,
+        constraint = basket_dca_vault.is_active @ KryptosError::DcaNotActive,
+        constraint = !basket_dca_vault.is_completed() @ KryptosError::DcaCompleted,
+    )]
+    pub basket_dca_vault: Account<'info, BasketDcaVault>,
+
+    /// Vault's input token account
+    #[account(
+        mut,
+        constraint = vault_input_token.key() == basket_dca_vault.input_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub vault_input_token: Account<'info, TokenAccount>,
+
+    /// Vault's output token account of the output being bought this execution
+    #[account(mut)]
+    pub vault_output_token: Account<'info, TokenAccount>,
+
+    /// Keeper's input token account (receives from vault for swap)
+    #[account(
+        mut,
+        constraint = keeper_input_token.owner == keeper.key() @ KryptosError::Unauthorized,
+    )]
+    pub keeper_input_token: Account<'info, TokenAccount>,
+
+    /// Keeper's output token account (sends swap result to vault)
+    #[account(
+        mut,
+        constraint = keeper_output_token.owner == keeper.key() @ KryptosError::Unauthorized,
+    )]
+    pub keeper_output_token: Account<'info, TokenAccount>,
+
+    /// Stats shard of the basket's input mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            basket_dca_vault.input_mint.as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExecuteBasketDcaParams {
+    /// Amount being swapped (with variance applied by keeper)
+    pub swap_amount: u64,
+    /// Amount of the selected output received from the swap
+    pub received_amount: u64,
+}
+
+pub fn handler_execute_basket_dca(
+    ctx: Context<ExecuteBasketDca>,
+    params: ExecuteBasketDcaParams,
+) -> Result<()> {
+    let basket = &mut ctx.accounts.basket_dca_vault;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Check if execution is allowed (time-based)
+    require!(
+        basket.can_execute(current_time),
+        KryptosError::DcaExecutionNotAllowed
+    );
+
+    // Validate swap amount
+    let remaining = basket.total_amount.saturating_sub(basket.total_spent);
+    require!(params.swap_amount > 0, KryptosError::InvalidAmount);
+    require!(params.swap_amount <= remaining, KryptosError::InvalidAmount);
+    require!(params.received_amount > 0, KryptosError::InvalidAmount);
+
+    // Rotate to the output furthest below its weight
+    let output_index = basket.next_output_index(params.swap_amount);
+    let output = basket.outputs[output_index];
+    require!(output.is_open(), KryptosError::BasketOutputNotOpen);
+    require_keys_eq!(
+        ctx.accounts.vault_output_token.key(),
+        output.vault,
+        KryptosError::TokenAccountMismatch
+    );
+
+    // Validate vault has enough funds
+    require!(
+        ctx.accounts.vault_input_token.amount >= params.swap_amount,
+        KryptosError::InsufficientFunds
+    );
+
+    // Validate keeper has the output tokens ready
+    require!(
+        ctx.accounts.keeper_output_token.amount >= params.received_amount,
+        KryptosError::InsufficientFunds
+    );
+
+    // Prepare PDA signer seeds
+    let authority_key = basket.authority;
+    let input_mint = basket.input_mint;
+    let nonce_bytes = basket.nonce.to_le_bytes();
+    let bump = basket.bump;
+
+    let seeds = &[
+        BasketDcaVault::SEED_PREFIX,
+        authority_key.as_ref(),
+        input_mint.as_ref(),
+        &nonce_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // 1. Transfer input tokens from vault to keeper
+    let transfer_to_keeper = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault_input_token.to_account_info(),
+            to: ctx.accounts.keeper_input_token.to_account_info(),
+            authority: basket.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer(transfer_to_keeper, params.swap_amount)?;
+
+    // 2. Transfer output tokens from keeper to the selected output vault
+    let transfer_to_vault = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.keeper_output_token.to_account_info(),
+            to: ctx.accounts.vault_output_token.to_account_info(),
+            authority: ctx.accounts.keeper.to_account_info(),
+        },
+    );
+    transfer(transfer_to_vault, params.received_amount)?;
+
+    // Update per-output and basket totals
+    let output = &mut basket.outputs[output_index];
+    output.total_spent = output
+        .total_spent
+        .checked_add(params.swap_amount)
+        .ok_or(KryptosError::MathOverflow)?;
+    output.total_received = output
+        .total_received
+        .checked_add(params.received_amount)
+        .ok_or(KryptosError::MathOverflow)?;
+    let output_mint = output.mint;
+
+    basket.total_spent = basket
+        .total_spent
+        .checked_add(params.swap_amount)
+        .ok_or(KryptosError::MathOverflow)?;
+
+    basket.execution_count = basket
+        .execution_count
+        .checked_add(1)
+        .ok_or(KryptosError::MathOverflow)?;
+
+    basket.last_execution = current_time;
+
+    // Book the execution on the input mint's stats shard
+    let protocol_stats = &mut ctx.accounts.protocol_stats;
+    protocol_stats.init_if_empty(input_mint, ctx.bumps.protocol_stats);
+    protocol_stats.record_execution(params.swap_amount);
+
+    // Calculate next execution time (randomized)
+    basket.schedule_next_execution(current_time);
+
+    // Check if the basket is completed
+    if basket.is_completed() {
+        basket.is_active = false;
+
+        emit!(BasketDcaCompleted {
+            vault: basket.key(),
+            authority: basket.authority,
+            total_spent: basket.total_spent,
+            execution_count: basket.execution_count,
+            completed_at: current_time,
+        });
+
+        msg!("Basket DCA completed!");
+    }
+
+    // Emit execution event
+    emit!(BasketDcaExecuted {
+        vault: basket.key(),
+        authority: basket.authority,
+        output_index: output_index as u8,
+        output_mint,
+        amount_spent: params.swap_amount,
+        amount_received: params.received_amount,
+        execution_count: basket.execution_count,
+        next_execution: basket.next_execution,
+        executed_at: current_time,
+    });
+
+    msg!("Basket DCA executed successfully");
+    msg!("Output #{} | Spent: {} | Received: {}", output_index, params.swap_amount, params.received_amount);
+    msg!("Execution #{} | Next: {}", basket.execution_count, basket.next_execution);
+
+    Ok(())
+}
+
+// ============================================
+// WITHDRAW FROM BASKET DCA
+// ============================================
+
+#[derive(Accounts)]
+pub struct WithdrawBasketDca<'info> {
+    /// Owner of the basket
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Basket vault to withdraw from
+    #[account(
+        mut,
+        constraint = basket_dca_vault.authority == authority.key() @ KryptosError::Unauthorized,
+    )]
+    pub basket_dca_vault: Account<'info, BasketDcaVault>,
+
+    /// Vault's input token account
+    #[account(
+        mut,
+        constraint = vault_input_token.key() == basket_dca_vault.input_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub vault_input_token: Account<'info, TokenAccount>,
+
+    /// User's input token account (to receive remaining input)
+    #[account(mut)]
+    pub user_input_token: Account<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: (vault output token, user output token) pairs for
+    // the outputs to claim
+}
+
+pub fn handler_withdraw_basket_dca<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawBasketDca<'info>>,
+) -> Result<()> {
+    let basket = &mut ctx.accounts.basket_dca_vault;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        ctx.remaining_accounts.len() % 2 == 0,
+        KryptosError::TokenAccountMismatch
+    );
+
+    // Get remaining balance
+    let remaining_input = ctx.accounts.vault_input_token.amount;
+
+    // Prepare PDA signer seeds
+    let authority_key = basket.authority;
+    let input_mint = basket.input_mint;
+    let nonce_bytes = basket.nonce.to_le_bytes();
+    let bump = basket.bump;
+
+    let seeds = &[
+        BasketDcaVault::SEED_PREFIX,
+        authority_key.as_ref(),
+        input_mint.as_ref(),
+        &nonce_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Transfer remaining input tokens back to user
+    if remaining_input > 0 {
+        let transfer_input_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_input_token.to_account_info(),
+                to: ctx.accounts.user_input_token.to_account_info(),
+                authority: basket.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_input_ctx, remaining_input)?;
+    }
+
+    // Transfer accumulated output tokens of every listed output to user
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let vault_output_token = Account::<TokenAccount>::try_from(&pair[0])?;
+        require!(
+            basket.outputs[..basket.num_outputs as usize]
+                .iter()
+                .any(|output| output.is_open() && output.vault == vault_output_token.key()),
+            KryptosError::TokenAccountMismatch
+        );
+
+        let accumulated_output = vault_output_token.amount;
+        if accumulated_output > 0 {
+            let transfer_output_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: pair[0].clone(),
+                    to: pair[1].clone(),
+                    authority: basket.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer(transfer_output_ctx, accumulated_output)?;
+
+            // Each output is its own mint, so it is reported on its own
+            emit!(BasketDcaOutputClaimed {
+                vault: basket.key(),
+                authority: basket.authority,
+                output_mint: vault_output_token.mint,
+                amount: accumulated_output,
+                claimed_at: current_time,
+            });
+            msg!("Output claimed: {} of {}", accumulated_output, vault_output_token.mint);
+        }
+    }
+
+    // Deactivate basket
+    basket.is_active = false;
+
+    // Emit events
+    emit!(DcaCancelled {
+        vault: basket.key(),
+        authority: basket.authority,
+        remaining_amount: remaining_input,
+        cancelled_at: current_time,
+    });
+
+    emit!(FundsWithdrawn {
+        vault: basket.key(),
+        authority: basket.authority,
+        amount: remaining_input,
+        vault_type: "BasketDCA".to_string(),
+        withdrawn_at: current_time,
+    });
+
+    msg!("Basket DCA withdrawn successfully");
+    msg!("Input returned: {}", remaining_input);
+
+    Ok(())
+}
+
+// ============================================
+// CLOSE BASKET DCA
+// ============================================
+
+#[derive(Accounts)]
+pub struct CloseBasketDca<'info> {
+    /// Owner of the basket
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Basket vault to close
+    #[account(
+        mut,
+        close = authority,
+        constraint = basket_dca_vault.authority == authority.key() @ KryptosError::Unauthorized,
+        constraint = !basket_dca_vault.is_active @ KryptosError::DcaNotActive,
+    )]
+    pub basket_dca_vault: Account<'info, BasketDcaVault>,
+
+    /// Vault's input token account (must be empty)
+    #[account(
+        mut,
+        constraint = vault_input_token.key() == basket_dca_vault.input_vault @ KryptosError::TokenAccountMismatch,
+        constraint = vault_input_token.amount == 0 @ KryptosError::DcaHasRemainingFunds,
+    )]
+    pub vault_input_token: Account<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: every opened output token account (must be empty),
+    // in output order
+}
+
+pub fn handler_close_basket_dca<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseBasketDca<'info>>,
+) -> Result<()> {
+    let basket = &ctx.accounts.basket_dca_vault;
+
+    // Every opened output account must be closed with the basket
+    let open_outputs: Vec<&BasketOutput> = basket.outputs[..basket.num_outputs as usize]
+        .iter()
+        .filter(|output| output.is_open())
+        .collect();
+    require!(
+        ctx.remaining_accounts.len() == open_outputs.len(),
+        KryptosError::TokenAccountMismatch
+    );
+
+    // Prepare PDA signer seeds
+    let authority_key = basket.authority;
+    let input_mint = basket.input_mint;
+    let nonce_bytes = basket.nonce.to_le_bytes();
+    let bump = basket.bump;
+
+    let seeds = &[
+        BasketDcaVault::SEED_PREFIX,
+        authority_key.as_ref(),
+        input_mint.as_ref(),
+        &nonce_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Close input token account
+    let close_input_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault_input_token.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: basket.to_account_info(),
+        },
+        signer_seeds,
+    );
+    close_account(close_input_ctx)?;
+
+    // Close output token accounts
+    for (output, account) in open_outputs.iter().zip(ctx.remaining_accounts.iter()) {
+        let vault_output_token = Account::<TokenAccount>::try_from(account)?;
+        require_keys_eq!(
+            vault_output_token.key(),
+            output.vault,
+            KryptosError::TokenAccountMismatch
+        );
+        require!(
+            vault_output_token.amount == 0,
+            KryptosError::DcaHasRemainingFunds
+        );
+
+        let close_output_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: account.clone(),
+                destination: ctx.accounts.authority.to_account_info(),
+                authority: basket.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(close_output_ctx)?;
+    }
+
+    msg!("Basket DCA vault closed successfully");
+    msg!("Rent reclaimed by: {}", ctx.accounts.authority.key());
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, BurnChecked, burn_checked};
+
+use crate::state::BurnReceipt;
+use crate::errors::KryptosError;
+use crate::events::TokensBurned;
+
+#[derive(Accounts)]
+pub struct BurnWithReceipt<'info> {
+    /// User burning the tokens
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Token mint being burned (SPL Token or Token-2022)
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// User's token account to burn from
+    #[account(
+        mut,
+        constraint = user_token.mint == mint.key() @ KryptosError::InvalidMint,
+        constraint = user_token.owner == authority.key() @ KryptosError::Unauthorized,
+    )]
+    pub user_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// Cumulative burn receipt for this user and mint
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = BurnReceipt::SPACE,
+        seeds = [
+            BurnReceipt::SEED_PREFIX,
+            authority.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub burn_receipt: Account<'info, BurnReceipt>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program owning the mint
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub(crate) fn handler(ctx: Context<BurnWithReceipt>, amount: u64) -> Result<()> {
+    require!(amount > 0, KryptosError::InvalidAmount);
+    require!(
+        ctx.accounts.user_token.amount >= amount,
+        KryptosError::InsufficientFunds
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Burn tokens from the user's account
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        BurnChecked {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.user_token.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        },
+    );
+    burn_checked(burn_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    // Record the burn (first burn initializes the receipt)
+    let burn_receipt = &mut ctx.accounts.burn_receipt;
+    if burn_receipt.burn_count == 0 {
+        burn_receipt.authority = ctx.accounts.authority.key();
+        burn_receipt.mint = ctx.accounts.mint.key();
+        burn_receipt.first_burned_at = current_time;
+        burn_receipt.bump = ctx.bumps.burn_receipt;
+    }
+    burn_receipt.last_amount = amount;
+    burn_receipt.last_burned_at = current_time;
+    burn_receipt.total_burned = burn_receipt
+        .total_burned
+        .checked_add(amount)
+        .ok_or(KryptosError::MathOverflow)?;
+    burn_receipt.burn_count = burn_receipt
+        .burn_count
+        .checked_add(1)
+        .ok_or(KryptosError::MathOverflow)?;
+
+    emit!(TokensBurned {
+        receipt: burn_receipt.key(),
+        authority: burn_receipt.authority,
+        mint: burn_receipt.mint,
+        amount,
+        total_burned: burn_receipt.total_burned,
+        burn_count: burn_receipt.burn_count,
+        burned_at: current_time,
+    });
+
+    msg!("Tokens burned successfully");
+    msg!("Burned: {} | Total: {}", amount, burn_receipt.total_burned);
+
+    Ok(())
+}
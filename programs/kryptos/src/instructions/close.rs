@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, CloseAccount, close_account};
 
-use crate::state::{DcaVault, IntentVault, IntentStatus};
+use crate::state::{DcaVault, IntentVault, IntentStatus, UserRegistry, ProtocolStats};
 use crate::errors::KryptosError;
+use crate::instructions::migrate::shrink_account;
 
 // ============================================
 // CLOSE DCA VAULT
@@ -18,15 +19,15 @@ pub struct CloseDca<'info> {
     #[account(
         mut,
         close = authority,
-        constraint = dca_vault.authority == authority.key() @ KryptosError::Unauthorized,
-        constraint = !dca_vault.is_active @ KryptosError::DcaNotActive,
+        constraint = dca_vault.load()?.authority == authority.key() @ KryptosError::Unauthorized,
+        constraint = !dca_vault.load()?.is_active() @ KryptosError::DcaNotActive,
     )]
-    pub dca_vault: Account<'info, DcaVault>,
+    pub dca_vault: AccountLoader<'info, DcaVault>,
 
     /// Vault's input token account (must be empty)
     #[account(
         mut,
-        constraint = vault_input_token.key() == dca_vault.input_vault @ KryptosError::TokenAccountMismatch,
+        constraint = vault_input_token.key() == dca_vault.load()?.input_vault @ KryptosError::TokenAccountMismatch,
         constraint = vault_input_token.amount == 0 @ KryptosError::DcaHasRemainingFunds,
     )]
     pub vault_input_token: Account<'info, TokenAccount>,
@@ -34,17 +35,41 @@ pub struct CloseDca<'info> {
     /// Vault's output token account (must be empty)
     #[account(
         mut,
-        constraint = vault_output_token.key() == dca_vault.output_vault @ KryptosError::TokenAccountMismatch,
+        constraint = vault_output_token.key() == dca_vault.load()?.output_vault @ KryptosError::TokenAccountMismatch,
         constraint = vault_output_token.amount == 0 @ KryptosError::DcaHasRemainingFunds,
     )]
     pub vault_output_token: Account<'info, TokenAccount>,
 
+    /// CHECK: owner's vault index PDA, always passed; updated and shrunk if
+    /// the owner has a registry, skipped if their vaults all predate it
+    #[account(
+        mut,
+        seeds = [
+            UserRegistry::SEED_PREFIX,
+            authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub user_registry: UncheckedAccount<'info>,
+
+    /// Stats shard of the vault's input mint (may be omitted unless the vault
+    /// is still counted as active)
+    #[account(
+        mut,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            vault_input_token.mint.as_ref(),
+        ],
+        bump = protocol_stats.bump,
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
     /// Token program
     pub token_program: Program<'info, Token>,
 }
 
 pub fn handler_close_dca(ctx: Context<CloseDca>) -> Result<()> {
-    let dca_vault = &ctx.accounts.dca_vault;
+    let mut dca_vault = *ctx.accounts.dca_vault.load()?;
 
     // Prepare PDA signer seeds
     let authority_key = dca_vault.authority;
@@ -67,7 +92,7 @@ pub fn handler_close_dca(ctx: Context<CloseDca>) -> Result<()> {
         CloseAccount {
             account: ctx.accounts.vault_input_token.to_account_info(),
             destination: ctx.accounts.authority.to_account_info(),
-            authority: dca_vault.to_account_info(),
+            authority: ctx.accounts.dca_vault.to_account_info(),
         },
         signer_seeds,
     );
@@ -79,14 +104,31 @@ pub fn handler_close_dca(ctx: Context<CloseDca>) -> Result<()> {
         CloseAccount {
             account: ctx.accounts.vault_output_token.to_account_info(),
             destination: ctx.accounts.authority.to_account_info(),
-            authority: dca_vault.to_account_info(),
+            authority: ctx.accounts.dca_vault.to_account_info(),
         },
         signer_seeds,
     );
     close_account(close_output_ctx)?;
 
-    msg!("DCA vault closed successfully");
-    msg!("Rent reclaimed by: {}", ctx.accounts.authority.key());
+    // Drop the vault from the owner's registry and its mint's open count
+    let vault_key = ctx.accounts.dca_vault.key();
+    unregister_vault(
+        &ctx.accounts.user_registry,
+        &ctx.accounts.authority,
+        |registry| registry.remove_dca(&vault_key),
+    )?;
+    if dca_vault.take_stats_count() {
+        ctx.accounts
+            .protocol_stats
+            .as_mut()
+            .ok_or(KryptosError::StatsShardRequired)?
+            .record_dca_deactivated();
+    }
+
+    if dca_vault.privacy_level().logs_enabled() {
+        msg!("DCA vault closed successfully");
+        msg!("Rent reclaimed by: {}", ctx.accounts.authority.key());
+    }
 
     Ok(())
 }
@@ -121,6 +163,30 @@ pub struct CloseIntent<'info> {
     )]
     pub vault_input_token: Account<'info, TokenAccount>,
 
+    /// CHECK: owner's vault index PDA, always passed; updated and shrunk if
+    /// the owner has a registry, skipped if their vaults all predate it
+    #[account(
+        mut,
+        seeds = [
+            UserRegistry::SEED_PREFIX,
+            authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub user_registry: UncheckedAccount<'info>,
+
+    /// Stats shard of the intent's input mint (may be omitted unless the
+    /// intent is still counted as active)
+    #[account(
+        mut,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            vault_input_token.mint.as_ref(),
+        ],
+        bump = protocol_stats.bump,
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
     /// Token program
     pub token_program: Program<'info, Token>,
 }
@@ -155,8 +221,53 @@ pub fn handler_close_intent(ctx: Context<CloseIntent>) -> Result<()> {
     );
     close_account(close_ctx)?;
 
-    msg!("Intent vault closed successfully");
-    msg!("Rent reclaimed by: {}", ctx.accounts.authority.key());
+    // Drop the vault from the owner's registry and its mint's open count
+    let vault_key = intent_vault.key();
+    unregister_vault(
+        &ctx.accounts.user_registry,
+        &ctx.accounts.authority,
+        |registry| registry.remove_intent(&vault_key),
+    )?;
+    if ctx.accounts.intent_vault.take_stats_count() {
+        ctx.accounts
+            .protocol_stats
+            .as_mut()
+            .ok_or(KryptosError::StatsShardRequired)?
+            .record_intent_deactivated();
+    }
+
+    if ctx.accounts.intent_vault.privacy_level.logs_enabled() {
+        msg!("Intent vault closed successfully");
+        msg!("Rent reclaimed by: {}", ctx.accounts.authority.key());
+    }
+
+    Ok(())
+}
+
+/// Remove a closed vault from the owner's registry and shrink the registry to
+/// fit, refunding the freed rent to the owner
+///
+/// Owners whose vaults all predate registries have nothing at the PDA.
+fn unregister_vault<'info>(
+    user_registry: &UncheckedAccount<'info>,
+    authority: &Signer<'info>,
+    remove: impl FnOnce(&mut UserRegistry),
+) -> Result<()> {
+    let registry_info = user_registry.to_account_info();
+    if registry_info.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(
+        *registry_info.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+
+    let mut registry = UserRegistry::try_deserialize(&mut &registry_info.try_borrow_data()?[..])?;
+    remove(&mut registry);
 
+    let space = registry.space_used();
+    shrink_account(&registry_info, &authority.to_account_info(), space)?;
+    registry.try_serialize(&mut &mut registry_info.try_borrow_mut_data()?[..])?;
     Ok(())
 }
@@ -2,30 +2,49 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
 use anchor_spl::associated_token::AssociatedToken;
 
-use crate::state::{IntentVault, IntentType, TriggerType, ExecutionStyle, IntentStatus};
+use crate::state::{IntentVault, IntentType, TriggerType, TriggerMode, ExecutionStyle, IntentStatus, PrivacyLevel, UserRegistry, ProtocolStats};
 use crate::errors::KryptosError;
 use crate::events::IntentCreated;
+use crate::entropy;
+use crate::instructions::migrate::grow_account;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateIntentParams {
     /// Unique nonce to allow multiple intents (user-provided)
     pub nonce: u64,
-    /// Intent type (Buy = 0, Sell = 1, Swap = 2)
-    pub intent_type: u8,
+    /// Intent type
+    pub intent_type: IntentType,
     /// Amount to use for the intent
     pub amount: u64,
-    /// Trigger type (PriceAbove = 0, PriceBelow = 1, PriceRange = 2)
-    pub trigger_type: u8,
-    /// Trigger price in USD (6 decimals)
+    /// Trigger type
+    pub trigger_type: TriggerType,
+    /// Trigger price in USD (6 decimals); initial peak for TrailingStop
     pub trigger_price: u64,
     /// Upper bound for PriceRange trigger (0 if not used)
     pub trigger_price_max: u64,
-    /// Execution style (Immediate = 0, Stealth = 1, Twap = 2)
-    pub execution_style: u8,
+    /// Execution style
+    pub execution_style: ExecutionStyle,
     /// Number of chunks for Stealth/TWAP (1 for Immediate)
     pub num_chunks: u8,
     /// Expiry time in seconds from now
     pub expiry_seconds: i64,
+    /// Pyth feed id of the output token (all zero = keeper-reported price)
+    pub output_feed_id: [u8; 32],
+    /// Interval between chunks for Stealth/TWAP (seconds)
+    pub chunk_interval_seconds: u32,
+    /// Chunk size and gap variance for Stealth (basis points, max 5000)
+    pub chunk_variance_bps: u16,
+    /// Pyth feed id of the input token (all zero = keeper-reported price)
+    pub input_feed_id: [u8; 32],
+    /// What the trigger prices are denominated in
+    pub trigger_mode: TriggerMode,
+    /// For CrossRate and Swap: decimals of the trigger prices (max 18)
+    pub rate_decimals: u8,
+    /// `TriggerReveal::commitment` of hidden trigger params (all zero = cleartext
+    /// trigger); shielded intents send PriceAbove with zero trigger prices
+    pub trigger_commitment: [u8; 32],
+    /// What logs and events reveal about the intent
+    pub privacy_level: PrivacyLevel,
 }
 
 #[derive(Accounts)]
@@ -78,6 +97,36 @@ pub struct CreateIntent<'info> {
     )]
     pub vault_input_token: Account<'info, TokenAccount>,
 
+    /// Owner's vault index (created on first use, grown per vault)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = user_registry.data_len().max(UserRegistry::space(0, 0)),
+        seeds = [
+            UserRegistry::SEED_PREFIX,
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub user_registry: Account<'info, UserRegistry>,
+
+    /// Stats shard of the input mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            input_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// CHECK: SlotHashes sysvar, seeds the first Stealth chunk draw
+    #[account(address = entropy::SLOT_HASHES_ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
     /// System program
     pub system_program: Program<'info, System>,
 
@@ -91,62 +140,74 @@ pub struct CreateIntent<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(ctx: Context<CreateIntent>, params: CreateIntentParams) -> Result<()> {
+pub(crate) fn handler(ctx: Context<CreateIntent>, params: CreateIntentParams) -> Result<()> {
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
 
     // Validate parameters
     require!(params.amount > 0, KryptosError::InvalidAmount);
-    require!(params.trigger_price > 0, KryptosError::InvalidTriggerPrice);
     require!(params.expiry_seconds > 0, KryptosError::InvalidExpiryTime);
 
-    // Parse intent type
-    let intent_type = match params.intent_type {
-        0 => IntentType::Buy,
-        1 => IntentType::Sell,
-        2 => IntentType::Swap,
-        _ => return Err(KryptosError::InvalidAmount.into()),
-    };
+    // Validate and store the trigger
+    set_trigger(&mut ctx.accounts.intent_vault, &params, current_time)?;
+
+    validate_rate(&params)?;
 
-    // Parse trigger type
-    let trigger_type = match params.trigger_type {
-        0 => TriggerType::PriceAbove,
-        1 => TriggerType::PriceBelow,
-        2 => {
-            // Validate price range
+    // Validate chunking for the execution style
+    match params.execution_style {
+        ExecutionStyle::Immediate => {
+            require!(params.num_chunks <= 1, KryptosError::InvalidChunkConfig);
+        }
+        ExecutionStyle::Twap | ExecutionStyle::Stealth => {
             require!(
-                params.trigger_price_max > params.trigger_price,
-                KryptosError::InvalidPriceRange
+                params.num_chunks >= 2
+                    && params.chunk_interval_seconds > 0
+                    && params.amount >= params.num_chunks as u64,
+                KryptosError::InvalidChunkConfig
             );
-            TriggerType::PriceRange
         }
-        _ => return Err(KryptosError::InvalidTriggerPrice.into()),
-    };
-
-    // Parse execution style
-    let execution_style = match params.execution_style {
-        0 => ExecutionStyle::Immediate,
-        1 => ExecutionStyle::Stealth,
-        2 => ExecutionStyle::Twap,
-        _ => ExecutionStyle::Immediate,
-    };
+    }
+    if params.execution_style == ExecutionStyle::Stealth {
+        require!(
+            params.chunk_variance_bps > 0 && params.chunk_variance_bps <= 5000,
+            KryptosError::InvalidVariance
+        );
+    }
 
     // Calculate expiry
     let expires_at = current_time + params.expiry_seconds;
 
+    // Index the vault in the owner's registry, growing it by one address
+    let registry_info = ctx.accounts.user_registry.to_account_info();
+    let registry_space = ctx.accounts.user_registry.space_after_push(false);
+    grow_account(
+        &registry_info,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        registry_space,
+    )?;
+    let user_registry = &mut ctx.accounts.user_registry;
+    if user_registry.authority == Pubkey::default() {
+        user_registry.authority = ctx.accounts.authority.key();
+        user_registry.bump = ctx.bumps.user_registry;
+    }
+    user_registry.register_intent(ctx.accounts.intent_vault.key(), params.nonce);
+
+    // Count the vault in its input mint's stats shard
+    let protocol_stats = &mut ctx.accounts.protocol_stats;
+    protocol_stats.init_if_empty(ctx.accounts.input_mint.key(), ctx.bumps.protocol_stats);
+    protocol_stats.record_intent_opened();
+
     // Initialize intent vault
     let intent_vault = &mut ctx.accounts.intent_vault;
     intent_vault.authority = ctx.accounts.authority.key();
     intent_vault.nonce = params.nonce;
-    intent_vault.intent_type = intent_type;
+    intent_vault.intent_type = params.intent_type;
     intent_vault.input_mint = ctx.accounts.input_mint.key();
     intent_vault.output_mint = ctx.accounts.output_mint.key();
     intent_vault.input_vault = ctx.accounts.vault_input_token.key();
     intent_vault.amount = params.amount;
-    intent_vault.trigger_type = trigger_type;
-    intent_vault.trigger_price = params.trigger_price;
-    intent_vault.trigger_price_max = params.trigger_price_max;
-    intent_vault.execution_style = execution_style;
+    intent_vault.execution_style = params.execution_style;
     intent_vault.num_chunks = params.num_chunks.max(1);
     intent_vault.chunks_executed = 0;
     intent_vault.expires_at = expires_at;
@@ -158,6 +219,33 @@ pub fn handler(ctx: Context<CreateIntent>, params: CreateIntentParams) -> Result
     intent_vault.total_received = 0;
     intent_vault.bump = ctx.bumps.intent_vault;
     intent_vault.vault_bump = ctx.bumps.vault_input_token;
+    intent_vault.output_feed_id = params.output_feed_id;
+    intent_vault.input_feed_id = params.input_feed_id;
+    intent_vault.trigger_mode = params.trigger_mode;
+    intent_vault.rate_decimals = params.rate_decimals;
+    intent_vault.chunk_interval = params.chunk_interval_seconds;
+    intent_vault.chunk_variance_bps = params.chunk_variance_bps;
+    intent_vault.next_chunk_at = 0;
+    intent_vault.privacy_level = params.privacy_level;
+    intent_vault.stats_counted = true;
+    intent_vault.version = IntentVault::LAYOUT_VERSION;
+
+    // Stealth draws its first chunk size now, so the keeper can read it before swapping
+    let slot_hash = entropy::recent_slot_hash(&ctx.accounts.slot_hashes)?;
+    let seed = entropy::vault_seed(&slot_hash, &intent_vault.key(), 0);
+    intent_vault.draw_chunk_amount(&seed);
+
+    // Oracle-bound intents need every feed their intent type watches
+    if intent_vault.has_price_feed() {
+        require!(
+            !intent_vault.uses_input_price() || intent_vault.input_feed_id != [0u8; 32],
+            KryptosError::OracleRequired
+        );
+        require!(
+            !intent_vault.uses_output_price() || intent_vault.output_feed_id != [0u8; 32],
+            KryptosError::OracleRequired
+        );
+    }
 
     // Transfer tokens from user to vault
     let transfer_ctx = CpiContext::new(
@@ -174,19 +262,232 @@ pub fn handler(ctx: Context<CreateIntent>, params: CreateIntentParams) -> Result
     emit!(IntentCreated {
         vault: intent_vault.key(),
         authority: intent_vault.authority,
-        intent_type: params.intent_type,
+        intent_type: params.intent_type as u8,
         input_mint: intent_vault.input_mint,
         output_mint: intent_vault.output_mint,
-        amount: intent_vault.amount,
-        trigger_price: intent_vault.trigger_price,
+        amount: intent_vault.privacy_level.event_amount(intent_vault.amount),
+        trigger_price: intent_vault.privacy_level.event_amount(intent_vault.trigger_price),
+        trigger_commitment: intent_vault.trigger_commitment,
         expires_at: intent_vault.expires_at,
         created_at: current_time,
     });
 
-    msg!("Intent created successfully");
-    msg!("Vault: {}", intent_vault.key());
-    msg!("Trigger price: {}", params.trigger_price);
-    msg!("Expires at: {}", expires_at);
+    if intent_vault.privacy_level.logs_enabled() {
+        msg!("Intent created successfully");
+        msg!("Vault: {}", intent_vault.key());
+        if intent_vault.is_shielded() {
+            msg!("Trigger: shielded");
+        } else {
+            msg!("Trigger price: {}", params.trigger_price);
+        }
+        msg!("Expires at: {}", expires_at);
+    }
+
+    Ok(())
+}
+
+/// Validate the feeds and decimals of rate-denominated triggers
+///
+/// CrossRate and Swap triggers are rates written with `rate_decimals`
+/// decimals; 0 would compare them against whole-token rates.
+fn validate_rate(params: &CreateIntentParams) -> Result<()> {
+    // Cross rates are computed on-chain from both oracle feeds
+    if params.trigger_mode == TriggerMode::CrossRate {
+        require!(
+            params.input_feed_id != [0u8; 32] && params.output_feed_id != [0u8; 32],
+            KryptosError::OracleRequired
+        );
+    }
+    if params.trigger_mode == TriggerMode::CrossRate || params.intent_type == IntentType::Swap {
+        require!(
+            params.rate_decimals > 0 && params.rate_decimals <= 18,
+            KryptosError::InvalidRateDecimals
+        );
+    }
+    Ok(())
+}
+
+/// Validate the trigger and store it on the vault
+///
+/// Shielded intents store only the commitment: the cleartext trigger fields
+/// must be left empty, so nothing about the trigger is readable before the
+/// keeper reveals it.
+fn set_trigger(
+    intent_vault: &mut IntentVault,
+    params: &CreateIntentParams,
+    current_time: i64,
+) -> Result<()> {
+    // Shielded intents keep the trigger out of both the account and the
+    // instruction data; it is validated when the keeper reveals it
+    if params.trigger_commitment != [0u8; 32] {
+        require!(
+            params.trigger_type == TriggerType::PriceAbove
+                && params.trigger_price == 0
+                && params.trigger_price_max == 0,
+            KryptosError::InvalidShieldedTrigger
+        );
+    } else {
+        require!(params.trigger_price > 0, KryptosError::InvalidTriggerPrice);
+    }
+
+    // Validate trigger
+    match params.trigger_type {
+        TriggerType::PriceAbove | TriggerType::PriceBelow => {}
+        TriggerType::PriceRange => {
+            require!(
+                params.trigger_price_max > params.trigger_price,
+                KryptosError::InvalidPriceRange
+            );
+        }
+        TriggerType::TrailingStop { trail_bps } => {
+            // Trailing stops track the peak from verified oracle prices only
+            require!(
+                trail_bps > 0 && trail_bps < 10_000,
+                KryptosError::InvalidTrailBps
+            );
+            require!(
+                params.input_feed_id != [0u8; 32] || params.output_feed_id != [0u8; 32],
+                KryptosError::OracleRequired
+            );
+        }
+    }
+
+    intent_vault.trigger_type = params.trigger_type;
+    intent_vault.trigger_price = params.trigger_price;
+    intent_vault.trigger_price_max = params.trigger_price_max;
+    intent_vault.trigger_commitment = params.trigger_commitment;
+
+    // Trailing stops start from the trigger price as their reference peak
+    intent_vault.peak_price = match params.trigger_type {
+        TriggerType::TrailingStop { .. } => params.trigger_price,
+        _ => 0,
+    };
+    intent_vault.peak_updated_at = current_time;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::TriggerReveal;
+
+    fn params(trigger_type: TriggerType, trigger_price: u64) -> CreateIntentParams {
+        CreateIntentParams {
+            nonce: 0,
+            intent_type: IntentType::Buy,
+            amount: 1_000,
+            trigger_type,
+            trigger_price,
+            trigger_price_max: 0,
+            execution_style: ExecutionStyle::Immediate,
+            num_chunks: 1,
+            expiry_seconds: 3_600,
+            output_feed_id: [0; 32],
+            chunk_interval_seconds: 0,
+            chunk_variance_bps: 0,
+            input_feed_id: [0; 32],
+            trigger_mode: TriggerMode::UsdPrice,
+            rate_decimals: 0,
+            trigger_commitment: [0; 32],
+            privacy_level: PrivacyLevel::default(),
+        }
+    }
+
+    #[test]
+    fn shielded_intent_stores_no_cleartext_trigger() {
+        let reveal = TriggerReveal {
+            trigger_type: TriggerType::PriceBelow,
+            trigger_price: 180_000_000,
+            trigger_price_max: 0,
+            salt: [7; 32],
+        };
+        let shielded = CreateIntentParams {
+            trigger_commitment: reveal.commitment().unwrap(),
+            ..params(TriggerType::PriceAbove, 0)
+        };
+
+        let mut vault = IntentVault::default();
+        set_trigger(&mut vault, &shielded, 100).unwrap();
+        assert!(vault.is_shielded());
+        assert!(vault.trigger_type == TriggerType::PriceAbove);
+        assert_eq!(vault.trigger_price, 0);
+        assert_eq!(vault.trigger_price_max, 0);
+        assert_eq!(vault.peak_price, 0);
+
+        // Only the owner's reveal opens it
+        assert!(vault.reveal_trigger(&reveal));
+        assert_eq!(vault.trigger_price, 180_000_000);
+
+        // Any cleartext trigger alongside the commitment is rejected
+        for leaky in [
+            CreateIntentParams { trigger_price: 180_000_000, ..shielded },
+            CreateIntentParams { trigger_price_max: 200_000_000, ..shielded },
+            CreateIntentParams { trigger_type: TriggerType::PriceBelow, ..shielded },
+        ] {
+            let mut vault = IntentVault::default();
+            assert_eq!(
+                set_trigger(&mut vault, &leaky, 100).err(),
+                Some(KryptosError::InvalidShieldedTrigger.into())
+            );
+            assert_eq!(vault.trigger_price, 0);
+        }
+    }
+
+    #[test]
+    fn cleartext_intent_stores_its_trigger() {
+        let mut vault = IntentVault::default();
+        set_trigger(&mut vault, &params(TriggerType::PriceBelow, 180_000_000), 100).unwrap();
+        assert!(!vault.is_shielded());
+        assert_eq!(vault.trigger_price, 180_000_000);
+
+        assert_eq!(
+            set_trigger(&mut vault, &params(TriggerType::PriceBelow, 0), 100).err(),
+            Some(KryptosError::InvalidTriggerPrice.into())
+        );
+    }
+
+    #[test]
+    fn rate_triggers_need_rate_decimals() {
+        // USD triggers of Buy/Sell intents ignore rate_decimals
+        assert!(validate_rate(&params(TriggerType::PriceBelow, 5)).is_ok());
+
+        let swap = CreateIntentParams {
+            intent_type: IntentType::Swap,
+            ..params(TriggerType::PriceBelow, 5)
+        };
+        let cross = CreateIntentParams {
+            trigger_mode: TriggerMode::CrossRate,
+            input_feed_id: [1; 32],
+            output_feed_id: [2; 32],
+            ..params(TriggerType::PriceBelow, 5)
+        };
+        for rate in [swap, cross] {
+            for (rate_decimals, valid) in [(0, false), (1, true), (18, true), (19, false)] {
+                let params = CreateIntentParams { rate_decimals, ..rate };
+                assert_eq!(
+                    validate_rate(&params).err(),
+                    (!valid).then(|| KryptosError::InvalidRateDecimals.into())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_codes_fail_to_decode() {
+        let data = params(TriggerType::PriceBelow, 5).try_to_vec().unwrap();
+        let decoded = CreateIntentParams::try_from_slice(&data).unwrap();
+        assert!(decoded.trigger_type == TriggerType::PriceBelow);
+        assert_eq!(decoded.trigger_price, 5);
+
+        // intent_type, trigger_type and execution_style codes
+        for offset in [8, 17, 34] {
+            let mut data = data.clone();
+            data[offset] = 9;
+            assert!(CreateIntentParams::try_from_slice(&data).is_err());
+        }
+
+        // Every field is required
+        assert!(CreateIntentParams::try_from_slice(&data[..data.len() - 1]).is_err());
+    }
+}
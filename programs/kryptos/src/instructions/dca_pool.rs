@@ -0,0 +1,712 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+
+use crate::state::{DcaPool, DcaPoolEpoch, DcaPoolPosition, ProtocolStats};
+use crate::errors::KryptosError;
+use crate::events::{DcaPoolCreated, DcaPoolDeposited, DcaPoolWithdrawn, DcaPoolExecuted, DcaPoolClaimed};
+
+// ============================================
+// INITIALIZE DCA POOL
+// ============================================
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeDcaPoolParams {
+    /// Seconds between executions (min 3600)
+    pub cadence_seconds: u32,
+    /// Share of the pooled input spent per execution (basis points, 1-5000)
+    pub spend_bps: u16,
+}
+
+#[derive(Accounts)]
+#[instruction(params: InitializeDcaPoolParams)]
+pub struct InitializeDcaPool<'info> {
+    /// Anyone may open a pool (pays rent)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Pool PDA, one per mint pair and cadence
+    #[account(
+        init,
+        payer = payer,
+        space = DcaPool::SPACE,
+        seeds = [
+            DcaPool::SEED_PREFIX,
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref(),
+            &params.cadence_seconds.to_le_bytes(),
+            &params.spend_bps.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub dca_pool: Account<'info, DcaPool>,
+
+    /// Input token mint (token to spend)
+    pub input_mint: Account<'info, Mint>,
+
+    /// Output token mint (token to buy)
+    pub output_mint: Account<'info, Mint>,
+
+    /// Pool's input token account (holds pooled deposits)
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"pool_input_vault",
+            dca_pool.key().as_ref(),
+        ],
+        bump,
+        token::mint = input_mint,
+        token::authority = dca_pool,
+    )]
+    pub pool_input_token: Account<'info, TokenAccount>,
+
+    /// Pool's output token account (holds output until claimed)
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"pool_output_vault",
+            dca_pool.key().as_ref(),
+        ],
+        bump,
+        token::mint = output_mint,
+        token::authority = dca_pool,
+    )]
+    pub pool_output_token: Account<'info, TokenAccount>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler_initialize_dca_pool(
+    ctx: Context<InitializeDcaPool>,
+    params: InitializeDcaPoolParams,
+) -> Result<()> {
+    // Validate parameters
+    require!(params.cadence_seconds >= 3600, KryptosError::InvalidPoolConfig);
+    require!(
+        params.spend_bps > 0 && params.spend_bps <= 5000,
+        KryptosError::InvalidPoolConfig
+    );
+    require_keys_neq!(
+        ctx.accounts.input_mint.key(),
+        ctx.accounts.output_mint.key(),
+        KryptosError::InvalidMint
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Initialize pool
+    let dca_pool = &mut ctx.accounts.dca_pool;
+    dca_pool.input_mint = ctx.accounts.input_mint.key();
+    dca_pool.output_mint = ctx.accounts.output_mint.key();
+    dca_pool.input_vault = ctx.accounts.pool_input_token.key();
+    dca_pool.output_vault = ctx.accounts.pool_output_token.key();
+    dca_pool.cadence_seconds = params.cadence_seconds;
+    dca_pool.spend_bps = params.spend_bps;
+    dca_pool.total_shares = 0;
+    dca_pool.total_input = 0;
+    dca_pool.acc_output_per_share = 0;
+    dca_pool.total_spent = 0;
+    dca_pool.total_received = 0;
+    dca_pool.execution_count = 0;
+    dca_pool.last_execution = 0;
+    dca_pool.next_execution = current_time + params.cadence_seconds as i64;
+    dca_pool.created_at = current_time;
+    dca_pool.bump = ctx.bumps.dca_pool;
+    dca_pool.input_vault_bump = ctx.bumps.pool_input_token;
+    dca_pool.output_vault_bump = ctx.bumps.pool_output_token;
+
+    emit!(DcaPoolCreated {
+        pool: dca_pool.key(),
+        input_mint: dca_pool.input_mint,
+        output_mint: dca_pool.output_mint,
+        cadence_seconds: dca_pool.cadence_seconds,
+        spend_bps: dca_pool.spend_bps,
+        created_at: current_time,
+    });
+
+    msg!("DCA pool created successfully");
+    msg!("Pool: {}", dca_pool.key());
+
+    Ok(())
+}
+
+// ============================================
+// DEPOSIT INTO DCA POOL
+// ============================================
+
+#[derive(Accounts)]
+pub struct DepositDcaPool<'info> {
+    /// Depositor
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Pool to deposit into
+    #[account(mut)]
+    pub dca_pool: Account<'info, DcaPool>,
+
+    /// Depositor's position (created on first deposit)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = DcaPoolPosition::SPACE,
+        seeds = [
+            DcaPoolPosition::SEED_PREFIX,
+            dca_pool.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump
+    )]
+    pub position: Account<'info, DcaPoolPosition>,
+
+    /// Record of the position's epoch (required once the pool has moved past it)
+    #[account(
+        seeds = [
+            DcaPoolEpoch::SEED_PREFIX,
+            dca_pool.key().as_ref(),
+            &position.epoch.to_le_bytes(),
+        ],
+        bump = pool_epoch.bump,
+    )]
+    pub pool_epoch: Option<Account<'info, DcaPoolEpoch>>,
+
+    /// Pool's input token account
+    #[account(
+        mut,
+        constraint = pool_input_token.key() == dca_pool.input_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub pool_input_token: Account<'info, TokenAccount>,
+
+    /// Depositor's input token account (source of funds)
+    #[account(
+        mut,
+        constraint = user_input_token.mint == dca_pool.input_mint @ KryptosError::InvalidMint,
+        constraint = user_input_token.owner == owner.key() @ KryptosError::Unauthorized,
+    )]
+    pub user_input_token: Account<'info, TokenAccount>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_deposit_dca_pool(ctx: Context<DepositDcaPool>, amount: u64) -> Result<()> {
+    require!(amount > 0, KryptosError::InvalidAmount);
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let dca_pool = &mut ctx.accounts.dca_pool;
+    let position = &mut ctx.accounts.position;
+
+    // First deposit initializes the position
+    if position.owner == Pubkey::default() {
+        position.pool = dca_pool.key();
+        position.owner = ctx.accounts.owner.key();
+        position.bump = ctx.bumps.position;
+        position.epoch = dca_pool.epoch;
+    }
+
+    roll_position(position, dca_pool, ctx.accounts.pool_epoch.as_deref())?;
+
+    // Settle output earned at the old share count
+    position.settle(dca_pool).ok_or(KryptosError::MathOverflow)?;
+
+    let shares = dca_pool
+        .shares_for_deposit(amount)
+        .ok_or(KryptosError::MathOverflow)?;
+    require!(shares > 0, KryptosError::InvalidAmount);
+
+    // Transfer tokens from depositor to pool
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_input_token.to_account_info(),
+            to: ctx.accounts.pool_input_token.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, amount)?;
+
+    // Update accounting
+    position.shares = position
+        .shares
+        .checked_add(shares)
+        .ok_or(KryptosError::MathOverflow)?;
+    dca_pool.total_shares = dca_pool
+        .total_shares
+        .checked_add(shares)
+        .ok_or(KryptosError::MathOverflow)?;
+    dca_pool.total_input = dca_pool
+        .total_input
+        .checked_add(amount)
+        .ok_or(KryptosError::MathOverflow)?;
+    position.sync_debt(dca_pool).ok_or(KryptosError::MathOverflow)?;
+
+    emit!(DcaPoolDeposited {
+        pool: dca_pool.key(),
+        owner: position.owner,
+        amount,
+        shares,
+        deposited_at: current_time,
+    });
+
+    msg!("Deposited {} into DCA pool", amount);
+
+    Ok(())
+}
+
+// ============================================
+// WITHDRAW FROM DCA POOL
+// ============================================
+
+#[derive(Accounts)]
+pub struct WithdrawDcaPool<'info> {
+    /// Depositor
+    pub owner: Signer<'info>,
+
+    /// Pool to withdraw from
+    #[account(mut)]
+    pub dca_pool: Account<'info, DcaPool>,
+
+    /// Depositor's position
+    #[account(
+        mut,
+        has_one = owner @ KryptosError::Unauthorized,
+        constraint = position.pool == dca_pool.key() @ KryptosError::Unauthorized,
+    )]
+    pub position: Account<'info, DcaPoolPosition>,
+
+    /// Record of the position's epoch (required once the pool has moved past it)
+    #[account(
+        seeds = [
+            DcaPoolEpoch::SEED_PREFIX,
+            dca_pool.key().as_ref(),
+            &position.epoch.to_le_bytes(),
+        ],
+        bump = pool_epoch.bump,
+    )]
+    pub pool_epoch: Option<Account<'info, DcaPoolEpoch>>,
+
+    /// Pool's input token account
+    #[account(
+        mut,
+        constraint = pool_input_token.key() == dca_pool.input_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub pool_input_token: Account<'info, TokenAccount>,
+
+    /// Depositor's input token account (receives unspent input)
+    #[account(mut)]
+    pub user_input_token: Account<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_withdraw_dca_pool(ctx: Context<WithdrawDcaPool>, shares: u128) -> Result<()> {
+    let dca_pool = &mut ctx.accounts.dca_pool;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    roll_position(position, dca_pool, ctx.accounts.pool_epoch.as_deref())?;
+
+    require!(
+        shares > 0 && shares <= position.shares,
+        KryptosError::InsufficientShares
+    );
+
+    // Settle output earned at the old share count
+    position.settle(dca_pool).ok_or(KryptosError::MathOverflow)?;
+
+    let amount = dca_pool
+        .input_for_shares(shares)
+        .ok_or(KryptosError::MathOverflow)?;
+
+    // Update accounting
+    position.shares -= shares;
+    dca_pool.total_shares -= shares;
+    dca_pool.total_input = dca_pool
+        .total_input
+        .checked_sub(amount)
+        .ok_or(KryptosError::MathOverflow)?;
+    position.sync_debt(dca_pool).ok_or(KryptosError::MathOverflow)?;
+
+    // Prepare PDA signer seeds
+    let input_mint = dca_pool.input_mint;
+    let output_mint = dca_pool.output_mint;
+    let cadence_bytes = dca_pool.cadence_seconds.to_le_bytes();
+    let spend_bytes = dca_pool.spend_bps.to_le_bytes();
+    let bump = dca_pool.bump;
+
+    let seeds = &[
+        DcaPool::SEED_PREFIX,
+        input_mint.as_ref(),
+        output_mint.as_ref(),
+        &cadence_bytes,
+        &spend_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Transfer unspent input back to depositor
+    if amount > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.pool_input_token.to_account_info(),
+                to: ctx.accounts.user_input_token.to_account_info(),
+                authority: dca_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, amount)?;
+    }
+
+    emit!(DcaPoolWithdrawn {
+        pool: dca_pool.key(),
+        owner: position.owner,
+        amount,
+        shares,
+        withdrawn_at: current_time,
+    });
+
+    msg!("Withdrew {} from DCA pool", amount);
+
+    Ok(())
+}
+
+// ============================================
+// CLAIM DCA POOL OUTPUT
+// ============================================
+
+#[derive(Accounts)]
+pub struct ClaimDcaPool<'info> {
+    /// Depositor
+    pub owner: Signer<'info>,
+
+    /// Pool to claim from
+    pub dca_pool: Account<'info, DcaPool>,
+
+    /// Depositor's position
+    #[account(
+        mut,
+        has_one = owner @ KryptosError::Unauthorized,
+        constraint = position.pool == dca_pool.key() @ KryptosError::Unauthorized,
+    )]
+    pub position: Account<'info, DcaPoolPosition>,
+
+    /// Record of the position's epoch (required once the pool has moved past it)
+    #[account(
+        seeds = [
+            DcaPoolEpoch::SEED_PREFIX,
+            dca_pool.key().as_ref(),
+            &position.epoch.to_le_bytes(),
+        ],
+        bump = pool_epoch.bump,
+    )]
+    pub pool_epoch: Option<Account<'info, DcaPoolEpoch>>,
+
+    /// Pool's output token account
+    #[account(
+        mut,
+        constraint = pool_output_token.key() == dca_pool.output_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub pool_output_token: Account<'info, TokenAccount>,
+
+    /// Depositor's output token account (receives claimed output)
+    #[account(mut)]
+    pub user_output_token: Account<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_claim_dca_pool(ctx: Context<ClaimDcaPool>) -> Result<()> {
+    let dca_pool = &ctx.accounts.dca_pool;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    roll_position(position, dca_pool, ctx.accounts.pool_epoch.as_deref())?;
+
+    // Settle output earned so far
+    position.settle(dca_pool).ok_or(KryptosError::MathOverflow)?;
+    position.sync_debt(dca_pool).ok_or(KryptosError::MathOverflow)?;
+
+    let amount = position.pending_output;
+    require!(amount > 0, KryptosError::NothingToClaim);
+
+    // Prepare PDA signer seeds
+    let input_mint = dca_pool.input_mint;
+    let output_mint = dca_pool.output_mint;
+    let cadence_bytes = dca_pool.cadence_seconds.to_le_bytes();
+    let spend_bytes = dca_pool.spend_bps.to_le_bytes();
+    let bump = dca_pool.bump;
+
+    let seeds = &[
+        DcaPool::SEED_PREFIX,
+        input_mint.as_ref(),
+        output_mint.as_ref(),
+        &cadence_bytes,
+        &spend_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Transfer pro-rata output to depositor
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.pool_output_token.to_account_info(),
+            to: ctx.accounts.user_output_token.to_account_info(),
+            authority: dca_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer(transfer_ctx, amount)?;
+
+    position.pending_output = 0;
+    position.total_claimed = position
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(KryptosError::MathOverflow)?;
+
+    emit!(DcaPoolClaimed {
+        pool: dca_pool.key(),
+        owner: position.owner,
+        amount,
+        claimed_at: current_time,
+    });
+
+    msg!("Claimed {} from DCA pool", amount);
+
+    Ok(())
+}
+
+/// Move a position out of an ended epoch, settling its shares against the
+/// epoch's final accumulator
+fn roll_position(
+    position: &mut DcaPoolPosition,
+    dca_pool: &DcaPool,
+    pool_epoch: Option<&DcaPoolEpoch>,
+) -> Result<()> {
+    if !position.is_stale(dca_pool) {
+        return Ok(());
+    }
+    let ended_acc_output_per_share = if position.shares == 0 {
+        0
+    } else {
+        pool_epoch
+            .ok_or(KryptosError::PoolEpochRequired)?
+            .acc_output_per_share
+    };
+    position
+        .retire(ended_acc_output_per_share, dca_pool.epoch)
+        .ok_or(KryptosError::MathOverflow)?;
+    Ok(())
+}
+
+// ============================================
+// EXECUTE DCA POOL
+// ============================================
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExecuteDcaPoolParams {
+    /// Amount being swapped (must equal the pool's execution amount)
+    pub swap_amount: u64,
+    /// Amount received from the swap
+    pub received_amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDcaPool<'info> {
+    /// Keeper/crank that triggers execution
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Pool to execute
+    #[account(mut)]
+    pub dca_pool: Account<'info, DcaPool>,
+
+    /// Pool's input token account
+    #[account(
+        mut,
+        constraint = pool_input_token.key() == dca_pool.input_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub pool_input_token: Account<'info, TokenAccount>,
+
+    /// Pool's output token account
+    #[account(
+        mut,
+        constraint = pool_output_token.key() == dca_pool.output_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub pool_output_token: Account<'info, TokenAccount>,
+
+    /// Keeper's input token account (receives from pool for swap)
+    #[account(
+        mut,
+        constraint = keeper_input_token.owner == keeper.key() @ KryptosError::Unauthorized,
+    )]
+    pub keeper_input_token: Account<'info, TokenAccount>,
+
+    /// Keeper's output token account (sends swap result to pool)
+    #[account(
+        mut,
+        constraint = keeper_output_token.owner == keeper.key() @ KryptosError::Unauthorized,
+    )]
+    pub keeper_output_token: Account<'info, TokenAccount>,
+
+    /// Stats shard of the pool's input mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            dca_pool.input_mint.as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// Record of the current epoch (required when this execution drains the pool)
+    #[account(
+        init,
+        payer = keeper,
+        space = DcaPoolEpoch::SPACE,
+        seeds = [
+            DcaPoolEpoch::SEED_PREFIX,
+            dca_pool.key().as_ref(),
+            &dca_pool.epoch.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pool_epoch: Option<Account<'info, DcaPoolEpoch>>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_execute_dca_pool(ctx: Context<ExecuteDcaPool>, params: ExecuteDcaPoolParams) -> Result<()> {
+    let dca_pool = &mut ctx.accounts.dca_pool;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Check if execution is allowed (time-based)
+    require!(
+        current_time >= dca_pool.next_execution
+            && dca_pool.total_shares > 0
+            && dca_pool.total_input > 0,
+        KryptosError::DcaExecutionNotAllowed
+    );
+
+    // Every execution spends the same fraction of the pool, then the rest
+    let execution_amount = dca_pool.execution_amount();
+    require!(
+        params.swap_amount == execution_amount,
+        KryptosError::InvalidAmount
+    );
+    require!(params.received_amount > 0, KryptosError::InvalidAmount);
+
+    // Validate keeper has the output tokens ready
+    require!(
+        ctx.accounts.keeper_output_token.amount >= params.received_amount,
+        KryptosError::InsufficientFunds
+    );
+
+    // Prepare PDA signer seeds
+    let input_mint = dca_pool.input_mint;
+    let output_mint = dca_pool.output_mint;
+    let cadence_bytes = dca_pool.cadence_seconds.to_le_bytes();
+    let spend_bytes = dca_pool.spend_bps.to_le_bytes();
+    let bump = dca_pool.bump;
+
+    let seeds = &[
+        DcaPool::SEED_PREFIX,
+        input_mint.as_ref(),
+        output_mint.as_ref(),
+        &cadence_bytes,
+        &spend_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // 1. Transfer input tokens from pool to keeper
+    let transfer_to_keeper = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.pool_input_token.to_account_info(),
+            to: ctx.accounts.keeper_input_token.to_account_info(),
+            authority: dca_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer(transfer_to_keeper, params.swap_amount)?;
+
+    // 2. Transfer output tokens from keeper to pool
+    let transfer_to_pool = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.keeper_output_token.to_account_info(),
+            to: ctx.accounts.pool_output_token.to_account_info(),
+            authority: ctx.accounts.keeper.to_account_info(),
+        },
+    );
+    transfer(transfer_to_pool, params.received_amount)?;
+
+    // Distribute the output across all shares and update pool state
+    dca_pool
+        .record_execution(params.swap_amount, params.received_amount)
+        .ok_or(KryptosError::MathOverflow)?;
+    dca_pool.last_execution = current_time;
+    dca_pool.next_execution = current_time + dca_pool.cadence_seconds as i64;
+
+    // The sweep spent the last input: keep the epoch's final accumulator for
+    // its positions and restart the shares so deposits reopen at 1:1
+    if dca_pool.is_drained() {
+        let pool_epoch = ctx
+            .accounts
+            .pool_epoch
+            .as_mut()
+            .ok_or(KryptosError::PoolEpochRequired)?;
+        pool_epoch.pool = dca_pool.key();
+        pool_epoch.epoch = dca_pool.epoch;
+        pool_epoch.acc_output_per_share = dca_pool.acc_output_per_share;
+        pool_epoch.bump = ctx.bumps.pool_epoch.ok_or(KryptosError::PoolEpochRequired)?;
+        dca_pool.start_next_epoch().ok_or(KryptosError::MathOverflow)?;
+        msg!("DCA pool epoch {} ended", pool_epoch.epoch);
+    }
+
+    // Book the execution on the input mint's stats shard
+    let protocol_stats = &mut ctx.accounts.protocol_stats;
+    protocol_stats.init_if_empty(input_mint, ctx.bumps.protocol_stats);
+    protocol_stats.record_execution(params.swap_amount);
+
+    emit!(DcaPoolExecuted {
+        pool: dca_pool.key(),
+        amount_spent: params.swap_amount,
+        amount_received: params.received_amount,
+        total_shares: dca_pool.total_shares,
+        next_execution: dca_pool.next_execution,
+        executed_at: current_time,
+    });
+
+    msg!("DCA pool executed successfully");
+    msg!("Spent: {} | Received: {}", params.swap_amount, params.received_amount);
+
+    Ok(())
+}
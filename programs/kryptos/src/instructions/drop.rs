@@ -0,0 +1,489 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer, CloseAccount, close_account};
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::{ed25519_program, sysvar};
+
+use crate::state::{DropVault, DropLockType};
+use crate::errors::KryptosError;
+use crate::events::{DropCreated, DropClaimed, DropRefunded};
+
+// ============================================
+// CREATE DROP
+// ============================================
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateDropParams {
+    /// How the claim key is shared (stored for clients, not validated)
+    pub lock_type: DropLockType,
+    /// Claim public key (for Secret drops, derived from the link secret)
+    pub claim_lock: [u8; 32],
+    /// Amount to drop
+    pub amount: u64,
+    /// Claim window in seconds (0 = default 7 days, max 30 days)
+    pub expiry_seconds: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateDropParams)]
+pub struct CreateDrop<'info> {
+    /// User funding the drop
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Drop vault PDA (the lock makes each link's vault unique)
+    #[account(
+        init,
+        payer = creator,
+        space = DropVault::SPACE,
+        seeds = [
+            DropVault::SEED_PREFIX,
+            params.claim_lock.as_ref(),
+        ],
+        bump
+    )]
+    pub drop_vault: Account<'info, DropVault>,
+
+    /// Token mint being dropped
+    pub mint: Account<'info, Mint>,
+
+    /// Creator's token account (source of funds)
+    #[account(
+        mut,
+        constraint = creator_token.mint == mint.key() @ KryptosError::InvalidMint,
+        constraint = creator_token.owner == creator.key() @ KryptosError::Unauthorized,
+    )]
+    pub creator_token: Account<'info, TokenAccount>,
+
+    /// Vault's token account (holds the drop)
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"drop_token_vault",
+            drop_vault.key().as_ref(),
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = drop_vault,
+    )]
+    pub vault_token: Account<'info, TokenAccount>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler_create_drop(ctx: Context<CreateDrop>, params: CreateDropParams) -> Result<()> {
+    // Validate parameters
+    require!(params.amount > 0, KryptosError::InvalidAmount);
+    require!(params.claim_lock != [0u8; 32], KryptosError::InvalidDropLock);
+    require!(
+        params.expiry_seconds >= 0 && params.expiry_seconds <= DropVault::MAX_EXPIRY_SECONDS,
+        KryptosError::InvalidExpiryTime
+    );
+
+    // Get current timestamp
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let expiry_seconds = if params.expiry_seconds == 0 {
+        DropVault::DEFAULT_EXPIRY_SECONDS
+    } else {
+        params.expiry_seconds
+    };
+    let expires_at = current_time + expiry_seconds;
+
+    // Initialize drop vault
+    let drop_vault = &mut ctx.accounts.drop_vault;
+    drop_vault.creator = ctx.accounts.creator.key();
+    drop_vault.mint = ctx.accounts.mint.key();
+    drop_vault.token_vault = ctx.accounts.vault_token.key();
+    drop_vault.amount = params.amount;
+    drop_vault.lock_type = params.lock_type;
+    drop_vault.claim_lock = params.claim_lock;
+    drop_vault.expires_at = expires_at;
+    drop_vault.created_at = current_time;
+    drop_vault.bump = ctx.bumps.drop_vault;
+    drop_vault.vault_bump = ctx.bumps.vault_token;
+
+    // Transfer tokens from creator to vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.creator_token.to_account_info(),
+            to: ctx.accounts.vault_token.to_account_info(),
+            authority: ctx.accounts.creator.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, params.amount)?;
+
+    // Emit event
+    emit!(DropCreated {
+        drop: drop_vault.key(),
+        creator: drop_vault.creator,
+        mint: drop_vault.mint,
+        amount: drop_vault.amount,
+        expires_at,
+        created_at: current_time,
+    });
+
+    msg!("Drop created successfully");
+    msg!("Drop: {}", drop_vault.key());
+    msg!("Expires at: {}", expires_at);
+
+    Ok(())
+}
+
+// ============================================
+// CLAIM DROP
+// ============================================
+
+#[derive(Accounts)]
+pub struct ClaimDrop<'info> {
+    /// Sponsor paying the transaction fees for the claimant
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// CHECK: Wallet receiving the drop; authorized by the claim key's signature
+    pub claimant: UncheckedAccount<'info>,
+
+    /// Drop to claim (rent returns to the creator)
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ KryptosError::Unauthorized,
+    )]
+    pub drop_vault: Account<'info, DropVault>,
+
+    /// CHECK: Drop creator receiving the rent, checked by `has_one`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Vault's token account
+    #[account(
+        mut,
+        constraint = vault_token.key() == drop_vault.token_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub vault_token: Account<'info, TokenAccount>,
+
+    /// Claimant's token account (created by the sponsor beforehand)
+    #[account(
+        mut,
+        constraint = claimant_token.owner == claimant.key() @ KryptosError::Unauthorized,
+        constraint = claimant_token.mint == drop_vault.mint @ KryptosError::InvalidMint,
+    )]
+    pub claimant_token: Account<'info, TokenAccount>,
+
+    /// CHECK: Instructions sysvar, used to find the ed25519 verification
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_claim_drop(ctx: Context<ClaimDrop>) -> Result<()> {
+    let drop_vault = &ctx.accounts.drop_vault;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        !drop_vault.is_expired(current_time),
+        KryptosError::DropExpired
+    );
+
+    // Unlock the drop: the claim key must have signed for this claimant, so
+    // nobody who sees the claim in flight can redirect it
+    let message = DropVault::claim_message(&drop_vault.key(), &ctx.accounts.claimant.key());
+    verify_claim_signature(
+        &ctx.accounts.instructions.to_account_info(),
+        &drop_vault.claim_lock,
+        &message,
+    )?;
+
+    // Prepare PDA signer seeds
+    let claim_lock = drop_vault.claim_lock;
+    let bump = drop_vault.bump;
+
+    let seeds = &[
+        DropVault::SEED_PREFIX,
+        claim_lock.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Transfer the drop to the claimant
+    let amount = ctx.accounts.vault_token.amount;
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault_token.to_account_info(),
+            to: ctx.accounts.claimant_token.to_account_info(),
+            authority: drop_vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer(transfer_ctx, amount)?;
+
+    // Close the token account; the drop account is closed by Anchor
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault_token.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: drop_vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    close_account(close_ctx)?;
+
+    emit!(DropClaimed {
+        drop: drop_vault.key(),
+        creator: drop_vault.creator,
+        claimant: ctx.accounts.claimant.key(),
+        sponsor: ctx.accounts.sponsor.key(),
+        amount,
+        claimed_at: current_time,
+    });
+
+    msg!("Drop claimed successfully");
+    msg!("Amount: {}", amount);
+
+    Ok(())
+}
+
+/// Require the instruction before this one to be an ed25519 verification of
+/// `message` signed by `claim_key`
+///
+/// The ed25519 program already rejected the transaction if the signature is
+/// invalid; this only checks it verified the right key and message, with all
+/// data inline in its own instruction.
+fn verify_claim_signature(
+    instructions: &AccountInfo,
+    claim_key: &[u8; 32],
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)?;
+    require!(current_index > 0, KryptosError::InvalidClaimSignature);
+
+    let verify_ix = load_instruction_at_checked(current_index as usize - 1, instructions)?;
+    require_keys_eq!(
+        verify_ix.program_id,
+        ed25519_program::ID,
+        KryptosError::InvalidClaimSignature
+    );
+
+    require!(
+        verifies_claim(&verify_ix.data, claim_key, message),
+        KryptosError::InvalidClaimSignature
+    );
+
+    Ok(())
+}
+
+/// Check that ed25519 program instruction data verifies exactly one
+/// signature by `claim_key` over `message`, with all data inline
+///
+/// Layout: num_signatures (u8), padding (u8), then one offsets entry of
+/// seven u16: signature offset/ix, public key offset/ix, message offset/size/ix
+fn verifies_claim(data: &[u8], claim_key: &[u8; 32], message: &[u8]) -> bool {
+    if data.len() < 16 || data[0] != 1 {
+        return false;
+    }
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let signature_ix = read_u16(4);
+    let public_key_offset = read_u16(6);
+    let public_key_ix = read_u16(8);
+    let message_offset = read_u16(10);
+    let message_size = read_u16(12);
+    let message_ix = read_u16(14);
+
+    let inline = u16::MAX as usize;
+    signature_ix == inline
+        && public_key_ix == inline
+        && message_ix == inline
+        && data.get(public_key_offset..public_key_offset + 32) == Some(claim_key.as_ref())
+        && data.get(message_offset..message_offset + message_size) == Some(message)
+}
+
+// ============================================
+// REFUND DROP
+// ============================================
+
+#[derive(Accounts)]
+pub struct RefundDrop<'info> {
+    /// Anyone may refund an expired drop
+    pub caller: Signer<'info>,
+
+    /// Expired drop to refund
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ KryptosError::Unauthorized,
+    )]
+    pub drop_vault: Account<'info, DropVault>,
+
+    /// CHECK: Drop creator receiving the rent, checked by `has_one`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Vault's token account
+    #[account(
+        mut,
+        constraint = vault_token.key() == drop_vault.token_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub vault_token: Account<'info, TokenAccount>,
+
+    /// Creator's token account (receives the refund)
+    #[account(
+        mut,
+        constraint = creator_token.owner == drop_vault.creator @ KryptosError::Unauthorized,
+        constraint = creator_token.mint == drop_vault.mint @ KryptosError::InvalidMint,
+    )]
+    pub creator_token: Account<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_refund_drop(ctx: Context<RefundDrop>) -> Result<()> {
+    let drop_vault = &ctx.accounts.drop_vault;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        drop_vault.is_expired(current_time),
+        KryptosError::DropNotExpired
+    );
+
+    // Prepare PDA signer seeds
+    let claim_lock = drop_vault.claim_lock;
+    let bump = drop_vault.bump;
+
+    let seeds = &[
+        DropVault::SEED_PREFIX,
+        claim_lock.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Return the drop to the creator
+    let amount = ctx.accounts.vault_token.amount;
+    if amount > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_token.to_account_info(),
+                to: ctx.accounts.creator_token.to_account_info(),
+                authority: drop_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, amount)?;
+    }
+
+    // Close the token account; the drop account is closed by Anchor
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault_token.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: drop_vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    close_account(close_ctx)?;
+
+    emit!(DropRefunded {
+        drop: drop_vault.key(),
+        creator: drop_vault.creator,
+        amount,
+        refunded_at: current_time,
+    });
+
+    msg!("Drop refunded successfully");
+    msg!("Amount returned: {}", amount);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ed25519 program data for one inline signature, as built by web3.js
+    fn ed25519_data(public_key: &[u8; 32], message: &[u8]) -> Vec<u8> {
+        let (public_key_offset, signature_offset) = (16u16, 48u16);
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            public_key_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(public_key);
+        data.extend_from_slice(&[9; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn accepts_claim_key_signature_for_claimant() {
+        let claim_key = [4u8; 32];
+        let drop_vault = Pubkey::new_from_array([1; 32]);
+        let claimant = Pubkey::new_from_array([2; 32]);
+        let message = DropVault::claim_message(&drop_vault, &claimant);
+
+        assert!(verifies_claim(&ed25519_data(&claim_key, &message), &claim_key, &message));
+    }
+
+    #[test]
+    fn rejects_front_run_claims() {
+        let claim_key = [4u8; 32];
+        let drop_vault = Pubkey::new_from_array([1; 32]);
+        let message = DropVault::claim_message(&drop_vault, &Pubkey::new_from_array([2; 32]));
+        let data = ed25519_data(&claim_key, &message);
+
+        // The signature was for another claimant, or by another key
+        let attacker = DropVault::claim_message(&drop_vault, &Pubkey::new_from_array([6; 32]));
+        assert!(!verifies_claim(&data, &claim_key, &attacker));
+        assert!(!verifies_claim(&data, &[5; 32], &message));
+    }
+
+    #[test]
+    fn rejects_malformed_verifications() {
+        let claim_key = [4u8; 32];
+        let message = [7u8; 64];
+        let valid = ed25519_data(&claim_key, &message);
+
+        // Several signatures, or none
+        let mut data = valid.clone();
+        data[0] = 2;
+        assert!(!verifies_claim(&data, &claim_key, &message));
+        assert!(!verifies_claim(&[0, 0], &claim_key, &message));
+
+        // Key or message read from another instruction
+        for index_at in [8, 14] {
+            let mut data = valid.clone();
+            data[index_at..index_at + 2].copy_from_slice(&0u16.to_le_bytes());
+            assert!(!verifies_claim(&data, &claim_key, &message));
+        }
+
+        // Truncated message and out-of-range offsets
+        assert!(!verifies_claim(&valid[..valid.len() - 1], &claim_key, &message));
+        let mut data = valid;
+        data[10..12].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(!verifies_claim(&data, &claim_key, &message));
+    }
+}
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
 
-use crate::state::DcaVault;
+use crate::state::{DcaVault, DcaStrategy, DcaSide, DcaSkipReason, ProtocolStats};
 use crate::errors::KryptosError;
-use crate::events::{DcaExecuted, DcaCompleted};
+use crate::events::{DcaExecuted, DcaCompleted, DcaSkipped};
+use crate::oracle;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct ExecuteDcaParams {
@@ -22,22 +23,22 @@ pub struct ExecuteDca<'info> {
     /// DCA vault to execute
     #[account(
         mut,
-        constraint = dca_vault.is_active @ KryptosError::DcaNotActive,
-        constraint = !dca_vault.is_completed() @ KryptosError::DcaCompleted,
+        constraint = dca_vault.load()?.is_active() @ KryptosError::DcaNotActive,
+        constraint = !dca_vault.load()?.is_completed() @ KryptosError::DcaCompleted,
     )]
-    pub dca_vault: Account<'info, DcaVault>,
+    pub dca_vault: AccountLoader<'info, DcaVault>,
 
     /// Vault's input token account (USDC etc)
     #[account(
         mut,
-        constraint = vault_input_token.key() == dca_vault.input_vault @ KryptosError::TokenAccountMismatch,
+        constraint = vault_input_token.key() == dca_vault.load()?.input_vault @ KryptosError::TokenAccountMismatch,
     )]
     pub vault_input_token: Account<'info, TokenAccount>,
 
     /// Vault's output token account (SOL wrapped, etc)
     #[account(
         mut,
-        constraint = vault_output_token.key() == dca_vault.output_vault @ KryptosError::TokenAccountMismatch,
+        constraint = vault_output_token.key() == dca_vault.load()?.output_vault @ KryptosError::TokenAccountMismatch,
     )]
     pub vault_output_token: Account<'info, TokenAccount>,
 
@@ -55,12 +56,47 @@ pub struct ExecuteDca<'info> {
     )]
     pub keeper_output_token: Account<'info, TokenAccount>,
 
+    /// Owner's output token account (Sell vaults stream proceeds here)
+    #[account(
+        mut,
+        constraint = authority_output_token.owner == dca_vault.load()?.authority @ KryptosError::Unauthorized,
+        constraint = authority_output_token.mint == dca_vault.load()?.output_mint @ KryptosError::InvalidMint,
+    )]
+    pub authority_output_token: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Pyth price update for the input token, required for Sell price
+    /// guards and value averaging and validated by `oracle::read_price`
+    pub input_price_update: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Pyth price update for the output token, required for Buy price
+    /// guards and value averaging and validated by `oracle::read_price`
+    pub output_price_update: Option<UncheckedAccount<'info>>,
+
+    /// Stats shard of the vault's input mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            vault_input_token.mint.as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
     /// Token program
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<ExecuteDca>, params: ExecuteDcaParams) -> Result<()> {
-    let dca_vault = &mut ctx.accounts.dca_vault;
+pub(crate) fn handler(ctx: Context<ExecuteDca>, params: ExecuteDcaParams) -> Result<()> {
+    // Work from a copy: the vault signs the transfers below, which needs its
+    // data unborrowed until the execution is booked
+    let vault_key = ctx.accounts.dca_vault.key();
+    let dca_vault = *ctx.accounts.dca_vault.load()?;
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
 
@@ -70,10 +106,48 @@ pub fn handler(ctx: Context<ExecuteDca>, params: ExecuteDcaParams) -> Result<()>
         KryptosError::DcaExecutionNotAllowed
     );
 
-    // Validate swap amount
+    // Read the oracle prices the vault's guard and strategy depend on
+    let input_price = if dca_vault.uses_input_price() {
+        oracle::read_feed_price(
+            ctx.accounts.input_price_update.as_ref(),
+            &dca_vault.input_feed_id,
+            current_time,
+        )?
+    } else {
+        0
+    };
+    let output_price = if dca_vault.uses_output_price() {
+        oracle::read_feed_price(
+            ctx.accounts.output_price_update.as_ref(),
+            &dca_vault.output_feed_id,
+            current_time,
+        )?
+    } else {
+        0
+    };
+
+    // Skip this execution (no funds move) when the price guard fails or
+    // value averaging is already at its target
+    let required_swap = match plan_execution(&dca_vault, input_price, output_price, current_time)? {
+        Execution::Skip { reason, price } => {
+            return skip_execution(
+                &mut *ctx.accounts.dca_vault.load_mut()?,
+                vault_key,
+                reason,
+                price,
+                current_time,
+            );
+        }
+        Execution::Swap { required_amount } => required_amount,
+    };
     let remaining = dca_vault.total_amount.saturating_sub(dca_vault.total_spent);
+
+    // Validate swap amount
     require!(params.swap_amount > 0, KryptosError::InvalidAmount);
     require!(params.swap_amount <= remaining, KryptosError::InvalidAmount);
+    if let Some(required_swap) = required_swap {
+        require!(params.swap_amount == required_swap, KryptosError::InvalidAmount);
+    }
     require!(params.received_amount > 0, KryptosError::InvalidAmount);
 
     // Validate vault has enough funds
@@ -109,32 +183,63 @@ pub fn handler(ctx: Context<ExecuteDca>, params: ExecuteDcaParams) -> Result<()>
         Transfer {
             from: ctx.accounts.vault_input_token.to_account_info(),
             to: ctx.accounts.keeper_input_token.to_account_info(),
-            authority: dca_vault.to_account_info(),
+            authority: ctx.accounts.dca_vault.to_account_info(),
         },
         signer_seeds,
     );
     transfer(transfer_to_keeper, params.swap_amount)?;
 
-    // 2. Transfer output tokens from keeper to vault
+    // 2. Transfer output tokens from keeper to vault (Sell: straight to the owner)
+    let output_destination = match dca_vault.side() {
+        DcaSide::Buy => ctx.accounts.vault_output_token.to_account_info(),
+        DcaSide::Sell => ctx
+            .accounts
+            .authority_output_token
+            .as_ref()
+            .ok_or(KryptosError::TokenAccountMismatch)?
+            .to_account_info(),
+    };
     let transfer_to_vault = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
         Transfer {
             from: ctx.accounts.keeper_output_token.to_account_info(),
-            to: ctx.accounts.vault_output_token.to_account_info(),
+            to: output_destination,
             authority: ctx.accounts.keeper.to_account_info(),
         },
     );
     transfer(transfer_to_vault, params.received_amount)?;
 
+    let input_mint = ctx.accounts.vault_input_token.mint;
+    ctx.accounts.protocol_stats.init_if_empty(input_mint, ctx.bumps.protocol_stats);
+    record_execution(
+        &mut *ctx.accounts.dca_vault.load_mut()?,
+        vault_key,
+        &mut ctx.accounts.protocol_stats,
+        params.swap_amount,
+        params.received_amount,
+        current_time,
+    )
+}
+
+/// Book a completed swap on the vault and its stats shard, schedule the next execution and emit
+/// the execution events
+pub(crate) fn record_execution(
+    dca_vault: &mut DcaVault,
+    vault_key: Pubkey,
+    protocol_stats: &mut ProtocolStats,
+    swap_amount: u64,
+    received_amount: u64,
+    current_time: i64,
+) -> Result<()> {
     // Update vault state
     dca_vault.total_spent = dca_vault
         .total_spent
-        .checked_add(params.swap_amount)
+        .checked_add(swap_amount)
         .ok_or(KryptosError::MathOverflow)?;
     
     dca_vault.total_received = dca_vault
         .total_received
-        .checked_add(params.received_amount)
+        .checked_add(received_amount)
         .ok_or(KryptosError::MathOverflow)?;
     
     dca_vault.execution_count = dca_vault
@@ -143,52 +248,190 @@ pub fn handler(ctx: Context<ExecuteDca>, params: ExecuteDcaParams) -> Result<()>
         .ok_or(KryptosError::MathOverflow)?;
     
     dca_vault.last_execution = current_time;
+    protocol_stats.record_execution(swap_amount);
 
     // Calculate next execution time (randomized)
-    let avg_executions = (dca_vault.min_executions + dca_vault.max_executions) / 2;
-    let base_interval = 604800i64 / (avg_executions as i64).max(1); // seconds in week / executions
-    
-    // Add randomness based on timestamp
-    let random_offset = ((current_time % 1000) * base_interval / 4000) as i64;
-    let next_interval = if current_time % 2 == 0 {
-        base_interval + random_offset
-    } else {
-        base_interval - random_offset
-    };
-    
-    dca_vault.next_execution = current_time + next_interval.max(3600); // minimum 1 hour
+    dca_vault.schedule_next_execution(current_time);
 
     // Check if DCA is completed
+    let privacy = dca_vault.privacy_level();
     let is_completed = dca_vault.is_completed();
     if is_completed {
-        dca_vault.is_active = false;
+        dca_vault.set_active(false);
+        if dca_vault.take_stats_count() {
+            protocol_stats.record_dca_deactivated();
+        }
         
         emit!(DcaCompleted {
-            vault: dca_vault.key(),
+            vault: vault_key,
             authority: dca_vault.authority,
-            total_spent: dca_vault.total_spent,
-            total_received: dca_vault.total_received,
+            total_spent: privacy.event_amount(dca_vault.total_spent),
+            total_received: privacy.event_amount(dca_vault.total_received),
             execution_count: dca_vault.execution_count,
             completed_at: current_time,
         });
 
-        msg!("DCA completed!");
+        if privacy.logs_enabled() {
+            msg!("DCA completed!");
+        }
     }
 
-    // Emit execution event
+    // Emit execution event (private vaults keep their schedule off the wire)
     emit!(DcaExecuted {
-        vault: dca_vault.key(),
+        vault: vault_key,
         authority: dca_vault.authority,
-        amount_spent: params.swap_amount,
-        amount_received: params.received_amount,
+        amount_spent: privacy.event_amount(swap_amount),
+        amount_received: privacy.event_amount(received_amount),
         execution_count: dca_vault.execution_count,
-        next_execution: dca_vault.next_execution,
+        next_execution: privacy.event_schedule(dca_vault.next_execution),
         executed_at: current_time,
     });
 
-    msg!("DCA executed successfully");
-    msg!("Spent: {} | Received: {}", params.swap_amount, params.received_amount);
-    msg!("Execution #{} | Next: {}", dca_vault.execution_count, dca_vault.next_execution);
+    if privacy.logs_enabled() {
+        msg!("DCA executed successfully");
+        msg!("Spent: {} | Received: {}", swap_amount, received_amount);
+        msg!("Execution #{} | Next: {}", dca_vault.execution_count, dca_vault.next_execution);
+    }
+
+    Ok(())
+}
+
+/// What a due execution does at the current prices
+#[derive(Debug, PartialEq, Eq)]
+enum Execution {
+    /// Skip it, reporting the price the decision was made on
+    Skip { reason: DcaSkipReason, price: u64 },
+    /// Swap, exactly `required_amount` if the strategy fixes it
+    Swap { required_amount: Option<u64> },
+}
+
+/// Decide whether a due execution swaps or is skipped: the price guard is
+/// checked on the side's token, then value averaging sizes the swap
+fn plan_execution(
+    dca_vault: &DcaVault,
+    input_price: u64,
+    output_price: u64,
+    current_time: i64,
+) -> Result<Execution> {
+    let guarded_price = dca_vault.guarded_price(input_price, output_price);
+    if let Some(reason) = dca_vault.check_price_guard(guarded_price) {
+        return Ok(Execution::Skip { reason, price: guarded_price });
+    }
+
+    // Value averaging must buy exactly what reaches the target (or the rest)
+    let required_amount = match dca_vault.strategy() {
+        DcaStrategy::FixedAmount => None,
+        DcaStrategy::ValueAveraging => {
+            let target_amount = dca_vault
+                .value_averaging_amount(input_price, output_price, current_time)
+                .ok_or(KryptosError::MathOverflow)?;
+            if target_amount == 0 {
+                return Ok(Execution::Skip {
+                    reason: DcaSkipReason::AboveTargetValue,
+                    price: output_price,
+                });
+            }
+            let remaining = dca_vault.total_amount.saturating_sub(dca_vault.total_spent);
+            Some(target_amount.min(remaining))
+        }
+    };
+    Ok(Execution::Swap { required_amount })
+}
+
+/// Skip a scheduled execution without moving funds
+fn skip_execution(
+    dca_vault: &mut DcaVault,
+    vault_key: Pubkey,
+    reason: DcaSkipReason,
+    current_price: u64,
+    current_time: i64,
+) -> Result<()> {
+    dca_vault.schedule_next_execution(current_time);
+
+    emit!(DcaSkipped {
+        vault: vault_key,
+        authority: dca_vault.authority,
+        reason,
+        current_price,
+        next_execution: dca_vault.privacy_level().event_schedule(dca_vault.next_execution),
+        skipped_at: current_time,
+    });
 
+    if dca_vault.privacy_level().logs_enabled() {
+        msg!("DCA execution skipped");
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Vault guarded between $100 and $200
+    fn guarded(side: DcaSide) -> DcaVault {
+        DcaVault {
+            side: side as u8,
+            min_price: 100_000_000,
+            max_price: 200_000_000,
+            total_amount: 1_000,
+            ..Default::default()
+        }
+    }
+
+    fn skip(reason: DcaSkipReason, price: u64) -> Execution {
+        Execution::Skip { reason, price }
+    }
+
+    #[test]
+    fn guard_skips_on_the_sides_token() {
+        // Buy vaults are guarded on the output price, Sell vaults on the input price
+        let buy = guarded(DcaSide::Buy);
+        assert_eq!(
+            plan_execution(&buy, 150_000_000, 250_000_000, 0).unwrap(),
+            skip(DcaSkipReason::PriceAboveMax, 250_000_000)
+        );
+        assert_eq!(
+            plan_execution(&buy, 250_000_000, 150_000_000, 0).unwrap(),
+            Execution::Swap { required_amount: None }
+        );
+
+        let sell = guarded(DcaSide::Sell);
+        assert_eq!(
+            plan_execution(&sell, 50_000_000, 150_000_000, 0).unwrap(),
+            skip(DcaSkipReason::PriceBelowMin, 50_000_000)
+        );
+        assert_eq!(
+            plan_execution(&sell, 150_000_000, 50_000_000, 0).unwrap(),
+            Execution::Swap { required_amount: None }
+        );
+    }
+
+    #[test]
+    fn value_averaging_skips_above_target() {
+        let vault = DcaVault {
+            strategy: DcaStrategy::ValueAveraging as u8,
+            min_executions: 7,
+            max_executions: 7,
+            target_increment: 100_000_000,
+            max_trade_amount: 500_000_000,
+            total_amount: 50_000_000,
+            total_received: 1_000_000_000,
+            input_decimals: 6,
+            output_decimals: 9,
+            ..Default::default()
+        };
+
+        // 1 SOL at $150 is past the $100 target
+        assert_eq!(
+            plan_execution(&vault, 1_000_000, 150_000_000, 0).unwrap(),
+            skip(DcaSkipReason::AboveTargetValue, 150_000_000)
+        );
+
+        // At $50 it is $50 short, capped at what is left in the vault
+        let short = DcaVault { total_amount: 30_000_000, ..vault };
+        assert_eq!(
+            plan_execution(&short, 1_000_000, 50_000_000, 0).unwrap(),
+            Execution::Swap { required_amount: Some(30_000_000) }
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+
+use crate::state::{DcaVault, DcaStrategy, DcaSide, PrivacyLevel, ProtocolStats};
+use crate::errors::KryptosError;
+use crate::events::DcaBatchExecuted;
+use crate::instructions::execute_dca::record_execution;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExecuteDcaBatchParams {
+    /// Amount swapped for each vault, in `remaining_accounts` order
+    pub swap_amounts: Vec<u64>,
+    /// Output received from the single netted swap, split pro-rata
+    pub total_received: u64,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDcaBatch<'info> {
+    /// Keeper/crank that triggers execution
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Keeper's input token account (receives from every vault for the swap)
+    #[account(
+        mut,
+        constraint = keeper_input_token.owner == keeper.key() @ KryptosError::Unauthorized,
+    )]
+    pub keeper_input_token: Account<'info, TokenAccount>,
+
+    /// Keeper's output token account (sends the swap result to every vault)
+    #[account(
+        mut,
+        constraint = keeper_output_token.owner == keeper.key() @ KryptosError::Unauthorized,
+    )]
+    pub keeper_output_token: Account<'info, TokenAccount>,
+
+    /// Stats shard of the batch's input mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            keeper_input_token.mint.as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: (DCA vault, vault input token, vault output token)
+    // groups, all on the same mint pair
+}
+
+/// Execute several Buy vaults of one mint pair with a single netted swap
+///
+/// Only fixed-amount vaults without price guards are batched; vaults that
+/// need oracle prices or stream proceeds go through `execute_dca`.
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteDcaBatch<'info>>,
+    params: ExecuteDcaBatchParams,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Validate batch shape
+    let groups = ctx.remaining_accounts.chunks(3);
+    require!(
+        !params.swap_amounts.is_empty()
+            && ctx.remaining_accounts.len() == params.swap_amounts.len() * 3,
+        KryptosError::InvalidBatch
+    );
+    require!(params.total_received > 0, KryptosError::InvalidAmount);
+
+    let total_swap = params
+        .swap_amounts
+        .iter()
+        .try_fold(0u64, |total, amount| total.checked_add(*amount))
+        .ok_or(KryptosError::MathOverflow)?;
+    require!(total_swap > 0, KryptosError::InvalidAmount);
+
+    // Validate keeper has the output tokens ready
+    require!(
+        ctx.accounts.keeper_output_token.amount >= params.total_received,
+        KryptosError::InsufficientFunds
+    );
+
+    let input_mint = ctx.accounts.keeper_input_token.mint;
+    ctx.accounts.protocol_stats.init_if_empty(input_mint, ctx.bumps.protocol_stats);
+
+    let mut mint_pair: Option<(Pubkey, Pubkey)> = None;
+    // The batch totals reveal every vault, so the strictest privacy level applies
+    let mut privacy = PrivacyLevel::Public;
+    let mut distributed = 0u64;
+    let last_index = params.swap_amounts.len() - 1;
+
+    for (index, (group, swap_amount)) in groups.zip(params.swap_amounts.iter()).enumerate() {
+        let swap_amount = *swap_amount;
+        let vault_loader = AccountLoader::<DcaVault>::try_from(&group[0])?;
+        let dca_vault = *vault_loader.load()?;
+        let vault_input_token = Account::<TokenAccount>::try_from(&group[1])?;
+
+        // Validate vault accounts
+        require_keys_eq!(
+            vault_input_token.key(),
+            dca_vault.input_vault,
+            KryptosError::TokenAccountMismatch
+        );
+        require_keys_eq!(
+            group[2].key(),
+            dca_vault.output_vault,
+            KryptosError::TokenAccountMismatch
+        );
+
+        // Every vault must share the netted mint pair
+        let pair = (dca_vault.input_mint, dca_vault.output_mint);
+        require!(
+            *mint_pair.get_or_insert(pair) == pair,
+            KryptosError::InvalidBatch
+        );
+
+        privacy = privacy.stricter(dca_vault.privacy_level());
+
+        // Only plain Buy vaults can be settled from the netted swap
+        require!(
+            dca_vault.side() == DcaSide::Buy
+                && dca_vault.strategy() == DcaStrategy::FixedAmount
+                && !dca_vault.has_price_guard(),
+            KryptosError::UnsupportedBatchVault
+        );
+
+        // Check if execution is allowed (time-based)
+        require!(dca_vault.is_active(), KryptosError::DcaNotActive);
+        require!(!dca_vault.is_completed(), KryptosError::DcaCompleted);
+        require!(
+            dca_vault.can_execute(current_time),
+            KryptosError::DcaExecutionNotAllowed
+        );
+
+        // Validate swap amount
+        let remaining = dca_vault.total_amount.saturating_sub(dca_vault.total_spent);
+        require!(swap_amount > 0, KryptosError::InvalidAmount);
+        require!(swap_amount <= remaining, KryptosError::InvalidAmount);
+        require!(
+            vault_input_token.amount >= swap_amount,
+            KryptosError::InsufficientFunds
+        );
+
+        // Split the swap output pro-rata; the last vault takes the rounding
+        let received_amount = if index == last_index {
+            params.total_received - distributed
+        } else {
+            (params.total_received as u128 * swap_amount as u128 / total_swap as u128) as u64
+        };
+        require!(received_amount > 0, KryptosError::InvalidAmount);
+        distributed += received_amount;
+
+        // Prepare PDA signer seeds
+        let authority_key = dca_vault.authority;
+        let input_mint = dca_vault.input_mint;
+        let output_mint = dca_vault.output_mint;
+        let bump = dca_vault.bump;
+
+        let seeds = &[
+            DcaVault::SEED_PREFIX,
+            authority_key.as_ref(),
+            input_mint.as_ref(),
+            output_mint.as_ref(),
+            &[bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // 1. Transfer input tokens from vault to keeper
+        let transfer_to_keeper = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: group[1].clone(),
+                to: ctx.accounts.keeper_input_token.to_account_info(),
+                authority: group[0].clone(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_to_keeper, swap_amount)?;
+
+        // 2. Transfer this vault's share of the output from keeper to vault
+        let transfer_to_vault = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.keeper_output_token.to_account_info(),
+                to: group[2].clone(),
+                authority: ctx.accounts.keeper.to_account_info(),
+            },
+        );
+        transfer(transfer_to_vault, received_amount)?;
+
+        // Book the execution (zero-copy writes go straight to the account)
+        record_execution(
+            &mut *vault_loader.load_mut()?,
+            vault_loader.key(),
+            &mut ctx.accounts.protocol_stats,
+            swap_amount,
+            received_amount,
+            current_time,
+        )?;
+    }
+
+    let (input_mint, output_mint) = mint_pair.ok_or(KryptosError::InvalidBatch)?;
+    emit!(DcaBatchExecuted {
+        input_mint,
+        output_mint,
+        vault_count: params.swap_amounts.len() as u32,
+        total_spent: privacy.event_amount(total_swap),
+        total_received: privacy.event_amount(params.total_received),
+        executed_at: current_time,
+    });
+
+    if privacy.logs_enabled() {
+        msg!("DCA batch executed: {} vaults", params.swap_amounts.len());
+    }
+
+    Ok(())
+}
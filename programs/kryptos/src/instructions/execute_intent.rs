@@ -1,18 +1,25 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
 
-use crate::state::{IntentVault, IntentStatus, ExecutionStyle};
+use crate::state::{IntentVault, IntentStatus, ExecutionStyle, TriggerType, TriggerReveal, ProtocolStats};
 use crate::errors::KryptosError;
-use crate::events::{IntentTriggered, IntentExecuted};
+use crate::events::{IntentTriggered, IntentExecuted, TrailingPeakUpdated};
+use crate::entropy;
+use crate::oracle;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct ExecuteIntentParams {
-    /// Current price from oracle (6 decimals USD)
+    /// Current trigger price reported by the keeper, already resolved for the
+    /// intent type (6 decimals); ignored when the intent has price feeds
     pub current_price: u64,
     /// Amount to swap this execution
     pub swap_amount: u64,
     /// Amount received from swap
     pub received_amount: u64,
+    /// Trigger params of a shielded intent, checked against its commitment
+    /// while it is still monitoring. Revealed params are public once the
+    /// transaction lands, so keepers should only submit when the trigger is met.
+    pub trigger_reveal: Option<TriggerReveal>,
 }
 
 #[derive(Accounts)]
@@ -57,14 +64,43 @@ pub struct ExecuteIntent<'info> {
     #[account(mut)]
     pub user_output_token: Account<'info, TokenAccount>,
 
+    /// CHECK: Pyth price update for the input token, required when the
+    /// intent watches it and validated by `oracle::read_price`
+    pub input_price_update: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Pyth price update for the output token, required when the
+    /// intent watches it and validated by `oracle::read_price`
+    pub output_price_update: Option<UncheckedAccount<'info>>,
+
+    /// Stats shard of the intent's input mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            vault_input_token.mint.as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// CHECK: SlotHashes sysvar, seeds the Stealth chunk draw
+    #[account(address = entropy::SLOT_HASHES_ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
     /// Token program
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<ExecuteIntent>, params: ExecuteIntentParams) -> Result<()> {
+pub(crate) fn handler(ctx: Context<ExecuteIntent>, params: ExecuteIntentParams) -> Result<()> {
     let intent_vault = &mut ctx.accounts.intent_vault;
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
+    let privacy = intent_vault.privacy_level;
 
     // Check if expired
     require!(
@@ -86,7 +122,45 @@ pub fn handler(ctx: Context<ExecuteIntent>, params: ExecuteIntentParams) -> Resu
 
     // If still monitoring, check trigger condition
     if intent_vault.status == IntentStatus::Monitoring {
-        let trigger_met = intent_vault.check_trigger(params.current_price);
+        // Shielded intents load their trigger from the keeper's reveal
+        if intent_vault.is_shielded() {
+            let reveal = params
+                .trigger_reveal
+                .as_ref()
+                .ok_or(KryptosError::TriggerRevealMismatch)?;
+            require!(
+                intent_vault.reveal_trigger(reveal),
+                KryptosError::TriggerRevealMismatch
+            );
+            validate_revealed_trigger(intent_vault)?;
+        }
+
+        // Intents bound to a feed only trust the oracle
+        let current_price = if intent_vault.has_price_feed() {
+            oracle_trigger_price(
+                intent_vault,
+                ctx.accounts.input_price_update.as_ref(),
+                ctx.accounts.output_price_update.as_ref(),
+                current_time,
+            )?
+        } else {
+            params.current_price
+        };
+
+        // Trailing stops first raise their peak with the fresh observation
+        let previous_peak = intent_vault.peak_price;
+        if intent_vault.observe_price(current_price, current_time) {
+            emit!(TrailingPeakUpdated {
+                vault: intent_vault.key(),
+                authority: intent_vault.authority,
+                previous_peak: privacy.event_amount(previous_peak),
+                new_peak: privacy.event_amount(intent_vault.peak_price),
+                stop_price: privacy.event_amount(intent_vault.trailing_stop_price()),
+                updated_at: current_time,
+            });
+        }
+
+        let trigger_met = intent_vault.check_trigger(current_price);
         
         require!(trigger_met, KryptosError::TriggerConditionNotMet);
 
@@ -97,18 +171,44 @@ pub fn handler(ctx: Context<ExecuteIntent>, params: ExecuteIntentParams) -> Resu
         emit!(IntentTriggered {
             vault: intent_vault.key(),
             authority: intent_vault.authority,
-            trigger_price: intent_vault.trigger_price,
-            current_price: params.current_price,
+            trigger_price: privacy.event_amount(intent_vault.trigger_price),
+            current_price: privacy.event_amount(current_price),
             triggered_at: current_time,
         });
 
-        msg!("Intent triggered at price: {}", params.current_price);
+        if privacy.logs_enabled() {
+            msg!("Intent triggered at price: {}", current_price);
+        }
     }
 
-    // Validate amounts
+    // Validate swap amount
+    let remaining = intent_vault.remaining_amount();
     require!(params.swap_amount > 0, KryptosError::InvalidAmount);
+    require!(params.swap_amount <= remaining, KryptosError::InvalidAmount);
     require!(params.received_amount > 0, KryptosError::InvalidAmount);
 
+    // The final chunk sweeps the remainder; earlier chunks must leave some behind
+    if intent_vault.is_final_chunk() {
+        require!(
+            params.swap_amount == remaining,
+            KryptosError::InvalidChunkAmount
+        );
+    } else {
+        require!(
+            params.swap_amount < remaining
+                && intent_vault.valid_chunk_amount(params.swap_amount),
+            KryptosError::InvalidChunkAmount
+        );
+    }
+
+    // Enforce chunk timing for split executions
+    if intent_vault.execution_style != ExecutionStyle::Immediate {
+        require!(
+            intent_vault.chunk_ready(current_time),
+            KryptosError::ChunkNotReady
+        );
+    }
+
     // Validate vault has enough funds
     require!(
         ctx.accounts.vault_input_token.amount >= params.swap_amount,
@@ -174,25 +274,92 @@ pub fn handler(ctx: Context<ExecuteIntent>, params: ExecuteIntentParams) -> Resu
         .ok_or(KryptosError::MathOverflow)?;
 
     intent_vault.chunks_executed += 1;
-
-    // Check if all chunks executed
-    if intent_vault.chunks_executed >= intent_vault.num_chunks {
+    let slot_hash = entropy::recent_slot_hash(&ctx.accounts.slot_hashes)?;
+    let seed = entropy::vault_seed(&slot_hash, &intent_vault.key(), intent_vault.chunks_executed as u64);
+    intent_vault.schedule_next_chunk(current_time, &seed);
+    let protocol_stats = &mut ctx.accounts.protocol_stats;
+    protocol_stats.init_if_empty(intent_vault.input_mint, ctx.bumps.protocol_stats);
+    protocol_stats.record_execution(params.swap_amount);
+
+    // Check if the full amount has been swapped
+    if intent_vault.is_completed() {
         intent_vault.status = IntentStatus::Executed;
         intent_vault.executed_at = current_time;
-        msg!("Intent fully executed!");
+        if intent_vault.take_stats_count() {
+            protocol_stats.record_intent_deactivated();
+        }
+        if privacy.logs_enabled() {
+            msg!("Intent fully executed!");
+        }
     }
 
     // Emit execution event
     emit!(IntentExecuted {
         vault: intent_vault.key(),
         authority: intent_vault.authority,
-        amount_spent: params.swap_amount,
-        amount_received: params.received_amount,
+        amount_spent: privacy.event_amount(params.swap_amount),
+        amount_received: privacy.event_amount(params.received_amount),
         executed_at: current_time,
     });
 
-    msg!("Intent execution successful");
-    msg!("Chunk {}/{}", intent_vault.chunks_executed, intent_vault.num_chunks);
+    if privacy.logs_enabled() {
+        msg!("Intent execution successful");
+        msg!("Chunk {}/{}", intent_vault.chunks_executed, intent_vault.num_chunks);
+    }
+
+    Ok(())
+}
 
+/// Apply create-time trigger validation to revealed trigger params
+fn validate_revealed_trigger(intent_vault: &IntentVault) -> Result<()> {
+    require!(intent_vault.trigger_price > 0, KryptosError::InvalidTriggerPrice);
+    if intent_vault.trigger_type == TriggerType::PriceRange {
+        require!(
+            intent_vault.trigger_price_max > intent_vault.trigger_price,
+            KryptosError::InvalidPriceRange
+        );
+    }
     Ok(())
 }
+
+/// Resolve an oracle-bound intent's trigger price from its Pyth price updates
+pub(crate) fn oracle_trigger_price(
+    intent_vault: &IntentVault,
+    input_price_update: Option<&UncheckedAccount>,
+    output_price_update: Option<&UncheckedAccount>,
+    current_time: i64,
+) -> Result<u64> {
+    if intent_vault.uses_cross_rate() {
+        let input_price = oracle::read_feed_oracle_price(
+            input_price_update,
+            &intent_vault.input_feed_id,
+            current_time,
+        )?;
+        let output_price = oracle::read_feed_oracle_price(
+            output_price_update,
+            &intent_vault.output_feed_id,
+            current_time,
+        )?;
+
+        let rate = intent_vault
+            .resolve_cross_rate(input_price, output_price)
+            .ok_or(KryptosError::MathOverflow)?;
+        return Ok(rate);
+    }
+
+    let input_price = if intent_vault.uses_input_price() {
+        oracle::read_feed_price(input_price_update, &intent_vault.input_feed_id, current_time)?
+    } else {
+        0
+    };
+    let output_price = if intent_vault.uses_output_price() {
+        oracle::read_feed_price(output_price_update, &intent_vault.output_feed_id, current_time)?
+    } else {
+        0
+    };
+
+    let current_price = intent_vault
+        .resolve_trigger_price(input_price, output_price)
+        .ok_or(KryptosError::MathOverflow)?;
+    Ok(current_price)
+}
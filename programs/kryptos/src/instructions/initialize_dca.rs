@@ -2,11 +2,12 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
 use anchor_spl::associated_token::AssociatedToken;
 
-use crate::state::DcaVault;
+use crate::state::{DcaVault, DcaStrategy, DcaSide, PrivacyLevel, UserRegistry, ProtocolStats};
 use crate::errors::KryptosError;
 use crate::events::DcaCreated;
+use crate::instructions::migrate::grow_account;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize)]
 pub struct InitializeDcaParams {
     /// Total amount to DCA (in smallest unit)
     pub total_amount: u64,
@@ -22,6 +23,89 @@ pub struct InitializeDcaParams {
     pub window_start_hour: u8,
     /// Execution window end hour (UTC, 0-23)
     pub window_end_hour: u8,
+    /// Pyth feed id of the output token (all zero = not priced)
+    pub output_feed_id: [u8; 32],
+    /// Skip executions while the guarded price is below this (6 decimals USD, 0 = off);
+    /// required as the price floor of Sell vaults
+    pub min_price: u64,
+    /// Skip executions while the guarded price is above this (6 decimals USD, 0 = off)
+    pub max_price: u64,
+    /// How much each execution buys
+    pub strategy: DcaStrategy,
+    /// Pyth feed id of the input token (required for ValueAveraging and Sell)
+    pub input_feed_id: [u8; 32],
+    /// For ValueAveraging: target USD value added per execution (6 decimals)
+    pub target_increment: u64,
+    /// For ValueAveraging: minimum input per execution
+    pub min_trade_amount: u64,
+    /// For ValueAveraging: maximum input per execution
+    pub max_trade_amount: u64,
+    /// Buy accumulates output; Sell exits input with streamed proceeds
+    pub side: DcaSide,
+    /// Slippage accepted when matched against opposing vaults (basis points,
+    /// max 1000, 0 = never matched); requires both feeds
+    pub max_slippage_bps: u16,
+    /// What logs and events reveal about executions
+    pub privacy_level: PrivacyLevel,
+}
+
+/// Fields added after the first release, sent after `window_end_hour`
+#[derive(AnchorDeserialize, Default)]
+struct InitializeDcaExtension {
+    output_feed_id: [u8; 32],
+    min_price: u64,
+    max_price: u64,
+    strategy: DcaStrategy,
+    input_feed_id: [u8; 32],
+    target_increment: u64,
+    min_trade_amount: u64,
+    max_trade_amount: u64,
+    side: DcaSide,
+    max_slippage_bps: u16,
+    privacy_level: PrivacyLevel,
+}
+
+/// Legacy clients stop after `window_end_hour`; the fields added since then
+/// decode to their defaults when absent.
+impl AnchorDeserialize for InitializeDcaParams {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let total_amount = u64::deserialize_reader(reader)?;
+        let amount_per_trade = u64::deserialize_reader(reader)?;
+        let variance_bps = u16::deserialize_reader(reader)?;
+        let min_executions = u8::deserialize_reader(reader)?;
+        let max_executions = u8::deserialize_reader(reader)?;
+        let window_start_hour = u8::deserialize_reader(reader)?;
+        let window_end_hour = u8::deserialize_reader(reader)?;
+
+        let mut extension = Vec::new();
+        reader.read_to_end(&mut extension)?;
+        let extension = if extension.is_empty() {
+            InitializeDcaExtension::default()
+        } else {
+            InitializeDcaExtension::try_from_slice(&extension)?
+        };
+
+        Ok(Self {
+            total_amount,
+            amount_per_trade,
+            variance_bps,
+            min_executions,
+            max_executions,
+            window_start_hour,
+            window_end_hour,
+            output_feed_id: extension.output_feed_id,
+            min_price: extension.min_price,
+            max_price: extension.max_price,
+            strategy: extension.strategy,
+            input_feed_id: extension.input_feed_id,
+            target_increment: extension.target_increment,
+            min_trade_amount: extension.min_trade_amount,
+            max_trade_amount: extension.max_trade_amount,
+            side: extension.side,
+            max_slippage_bps: extension.max_slippage_bps,
+            privacy_level: extension.privacy_level,
+        })
+    }
 }
 
 #[derive(Accounts)]
@@ -44,7 +128,7 @@ pub struct InitializeDca<'info> {
         ],
         bump
     )]
-    pub dca_vault: Account<'info, DcaVault>,
+    pub dca_vault: AccountLoader<'info, DcaVault>,
 
     /// Input token mint (token to spend)
     pub input_mint: Account<'info, Mint>,
@@ -88,6 +172,32 @@ pub struct InitializeDca<'info> {
     )]
     pub vault_output_token: Account<'info, TokenAccount>,
 
+    /// Owner's vault index (created on first use, grown per vault)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = user_registry.data_len().max(UserRegistry::space(0, 0)),
+        seeds = [
+            UserRegistry::SEED_PREFIX,
+            authority.key().as_ref(),
+        ],
+        bump
+    )]
+    pub user_registry: Account<'info, UserRegistry>,
+
+    /// Stats shard of the input mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            input_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
     /// System program
     pub system_program: Program<'info, System>,
 
@@ -101,19 +211,9 @@ pub struct InitializeDca<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(ctx: Context<InitializeDca>, params: InitializeDcaParams) -> Result<()> {
+pub(crate) fn handler(ctx: Context<InitializeDca>, params: InitializeDcaParams) -> Result<()> {
     // Validate parameters
-    require!(params.total_amount > 0, KryptosError::InvalidAmount);
-    require!(params.amount_per_trade > 0, KryptosError::InvalidAmount);
-    require!(params.variance_bps <= 5000, KryptosError::InvalidVariance);
-    require!(
-        params.min_executions > 0 && params.max_executions >= params.min_executions,
-        KryptosError::InvalidExecutionRange
-    );
-    require!(
-        params.window_start_hour < 24 && params.window_end_hour < 24,
-        KryptosError::InvalidTimeWindow
-    );
+    validate_params(&params)?;
 
     // Get current timestamp
     let clock = Clock::get()?;
@@ -124,8 +224,30 @@ pub fn handler(ctx: Context<InitializeDca>, params: InitializeDcaParams) -> Resu
     // The keeper will randomize subsequent executions
     let next_execution = current_time + 3600; // 1 hour from now
 
+    // Index the vault in the owner's registry, growing it by one address
+    let registry_info = ctx.accounts.user_registry.to_account_info();
+    let registry_space = ctx.accounts.user_registry.space_after_push(true);
+    grow_account(
+        &registry_info,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        registry_space,
+    )?;
+    let user_registry = &mut ctx.accounts.user_registry;
+    if user_registry.authority == Pubkey::default() {
+        user_registry.authority = ctx.accounts.authority.key();
+        user_registry.bump = ctx.bumps.user_registry;
+    }
+    user_registry.register_dca(ctx.accounts.dca_vault.key());
+
+    // Count the vault in its input mint's stats shard
+    let protocol_stats = &mut ctx.accounts.protocol_stats;
+    protocol_stats.init_if_empty(ctx.accounts.input_mint.key(), ctx.bumps.protocol_stats);
+    protocol_stats.record_dca_opened();
+
     // Initialize DCA vault
-    let dca_vault = &mut ctx.accounts.dca_vault;
+    let vault_key = ctx.accounts.dca_vault.key();
+    let dca_vault = &mut ctx.accounts.dca_vault.load_init()?;
     dca_vault.authority = ctx.accounts.authority.key();
     dca_vault.input_mint = ctx.accounts.input_mint.key();
     dca_vault.output_mint = ctx.accounts.output_mint.key();
@@ -143,11 +265,26 @@ pub fn handler(ctx: Context<InitializeDca>, params: InitializeDcaParams) -> Resu
     dca_vault.execution_count = 0;
     dca_vault.last_execution = 0;
     dca_vault.next_execution = next_execution;
-    dca_vault.is_active = true;
+    dca_vault.set_active(true);
+    dca_vault.stats_counted = 1;
     dca_vault.created_at = current_time;
     dca_vault.bump = ctx.bumps.dca_vault;
     dca_vault.input_vault_bump = ctx.bumps.vault_input_token;
     dca_vault.output_vault_bump = ctx.bumps.vault_output_token;
+    dca_vault.output_feed_id = params.output_feed_id;
+    dca_vault.min_price = params.min_price;
+    dca_vault.max_price = params.max_price;
+    dca_vault.strategy = params.strategy as u8;
+    dca_vault.input_feed_id = params.input_feed_id;
+    dca_vault.target_increment = params.target_increment;
+    dca_vault.min_trade_amount = params.min_trade_amount;
+    dca_vault.max_trade_amount = params.max_trade_amount;
+    dca_vault.input_decimals = ctx.accounts.input_mint.decimals;
+    dca_vault.output_decimals = ctx.accounts.output_mint.decimals;
+    dca_vault.side = params.side as u8;
+    dca_vault.max_slippage_bps = params.max_slippage_bps;
+    dca_vault.privacy_level = params.privacy_level as u8;
+    dca_vault.version = DcaVault::LAYOUT_VERSION;
 
     // Transfer tokens from user to vault
     let transfer_ctx = CpiContext::new(
@@ -162,17 +299,157 @@ pub fn handler(ctx: Context<InitializeDca>, params: InitializeDcaParams) -> Resu
 
     // Emit event
     emit!(DcaCreated {
-        vault: dca_vault.key(),
+        vault: vault_key,
         authority: dca_vault.authority,
         input_mint: dca_vault.input_mint,
         output_mint: dca_vault.output_mint,
-        total_amount: dca_vault.total_amount,
+        total_amount: dca_vault.privacy_level().event_amount(dca_vault.total_amount),
         created_at: current_time,
     });
 
-    msg!("DCA vault created successfully");
-    msg!("Vault: {}", dca_vault.key());
-    msg!("Total amount: {}", params.total_amount);
+    if dca_vault.privacy_level().logs_enabled() {
+        msg!("DCA vault created successfully");
+        msg!("Vault: {}", vault_key);
+        msg!("Total amount: {}", params.total_amount);
+    }
+
+    Ok(())
+}
+
+/// Validate the vault config: amounts, schedule, price guards and the
+/// oracle feeds each strategy and side depends on
+fn validate_params(params: &InitializeDcaParams) -> Result<()> {
+    require!(params.total_amount > 0, KryptosError::InvalidAmount);
+    require!(
+        params.amount_per_trade > 0 || params.strategy == DcaStrategy::ValueAveraging,
+        KryptosError::InvalidAmount
+    );
+    require!(params.variance_bps <= 5000, KryptosError::InvalidVariance);
+    require!(
+        params.min_executions > 0 && params.max_executions >= params.min_executions,
+        KryptosError::InvalidExecutionRange
+    );
+    require!(
+        params.window_start_hour < 24 && params.window_end_hour < 24,
+        KryptosError::InvalidTimeWindow
+    );
+
+    // Sell vaults never dump below their price floor
+    if params.side == DcaSide::Sell {
+        require!(params.min_price > 0, KryptosError::InvalidPriceRange);
+        require!(
+            params.strategy == DcaStrategy::FixedAmount,
+            KryptosError::UnsupportedDcaStrategy
+        );
+    }
+
+    // Price guards are evaluated against the oracle only, on the token the
+    // vault buys (Buy) or sells (Sell)
+    if params.min_price > 0 || params.max_price > 0 {
+        let guarded_feed_id = match params.side {
+            DcaSide::Buy => params.output_feed_id,
+            DcaSide::Sell => params.input_feed_id,
+        };
+        require!(
+            guarded_feed_id != [0u8; 32],
+            KryptosError::OracleRequired
+        );
+    }
+    if params.min_price > 0 && params.max_price > 0 {
+        require!(
+            params.min_price < params.max_price,
+            KryptosError::InvalidPriceRange
+        );
+    }
+
+    // Value averaging values the position with both oracle feeds
+    if params.strategy == DcaStrategy::ValueAveraging {
+        require!(
+            params.input_feed_id != [0u8; 32] && params.output_feed_id != [0u8; 32],
+            KryptosError::OracleRequired
+        );
+        require!(params.target_increment > 0, KryptosError::InvalidAmount);
+        require!(
+            params.max_trade_amount > 0 && params.min_trade_amount <= params.max_trade_amount,
+            KryptosError::InvalidTradeLimits
+        );
+    }
+
+    // Matching values both sides of the trade with the oracle feeds
+    if params.max_slippage_bps > 0 {
+        require!(params.max_slippage_bps <= 1000, KryptosError::InvalidSlippage);
+        require!(
+            params.input_feed_id != [0u8; 32] && params.output_feed_id != [0u8; 32],
+            KryptosError::OracleRequired
+        );
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sell vault with a $100 floor, priced on the input feed
+    fn sell() -> InitializeDcaParams {
+        InitializeDcaParams {
+            total_amount: 1_000,
+            amount_per_trade: 100,
+            variance_bps: 0,
+            min_executions: 1,
+            max_executions: 7,
+            window_start_hour: 0,
+            window_end_hour: 23,
+            output_feed_id: [0; 32],
+            min_price: 100_000_000,
+            max_price: 0,
+            strategy: DcaStrategy::FixedAmount,
+            input_feed_id: [1; 32],
+            target_increment: 0,
+            min_trade_amount: 0,
+            max_trade_amount: 0,
+            side: DcaSide::Sell,
+            max_slippage_bps: 0,
+            privacy_level: PrivacyLevel::default(),
+        }
+    }
+
+    #[test]
+    fn sell_needs_a_price_floor() {
+        assert!(validate_params(&sell()).is_ok());
+        assert_eq!(
+            validate_params(&InitializeDcaParams { min_price: 0, ..sell() }).err(),
+            Some(KryptosError::InvalidPriceRange.into())
+        );
+        // A ceiling alone is no floor
+        assert_eq!(
+            validate_params(&InitializeDcaParams { min_price: 0, max_price: 200_000_000, ..sell() }).err(),
+            Some(KryptosError::InvalidPriceRange.into())
+        );
+
+        // The floor is checked on the input token's feed
+        assert_eq!(
+            validate_params(&InitializeDcaParams { input_feed_id: [0; 32], output_feed_id: [2; 32], ..sell() }).err(),
+            Some(KryptosError::OracleRequired.into())
+        );
+    }
+
+    #[test]
+    fn sell_only_spends_fixed_amounts() {
+        let averaging = InitializeDcaParams {
+            strategy: DcaStrategy::ValueAveraging,
+            output_feed_id: [2; 32],
+            target_increment: 100_000_000,
+            max_trade_amount: 500,
+            ..sell()
+        };
+        assert_eq!(
+            validate_params(&averaging).err(),
+            Some(KryptosError::UnsupportedDcaStrategy.into())
+        );
+
+        // The same strategy is fine for Buy vaults
+        assert!(validate_params(&InitializeDcaParams { side: DcaSide::Buy, min_price: 0, ..averaging }).is_ok());
+    }
+}
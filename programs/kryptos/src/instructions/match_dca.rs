@@ -0,0 +1,368 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+
+use crate::state::{DcaVault, DcaSide, ProtocolStats};
+use crate::errors::KryptosError;
+use crate::events::DcaMatched;
+use crate::instructions::execute_dca::record_execution;
+use crate::oracle;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MatchDcaParams {
+    /// Input of vault A delivered to vault B
+    pub matched_a: u64,
+    /// Input of vault B delivered to vault A
+    pub matched_b: u64,
+    /// Vault A's unmatched remainder swapped externally by the keeper (0 = none)
+    pub external_swap_amount: u64,
+    /// Amount received from the external swap
+    pub external_received_amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct MatchDca<'info> {
+    /// Keeper/crank that triggers execution
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// DCA vault A (carries any net imbalance)
+    #[account(
+        mut,
+        constraint = dca_vault_a.load()?.is_active() @ KryptosError::DcaNotActive,
+        constraint = !dca_vault_a.load()?.is_completed() @ KryptosError::DcaCompleted,
+    )]
+    pub dca_vault_a: AccountLoader<'info, DcaVault>,
+
+    /// Vault A's input token account
+    #[account(
+        mut,
+        constraint = vault_a_input_token.key() == dca_vault_a.load()?.input_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub vault_a_input_token: Account<'info, TokenAccount>,
+
+    /// Where vault A's output goes (its output vault, or the owner's account for Sell)
+    #[account(mut)]
+    pub vault_a_output_destination: Account<'info, TokenAccount>,
+
+    /// Opposing DCA vault B
+    #[account(
+        mut,
+        constraint = dca_vault_b.load()?.is_active() @ KryptosError::DcaNotActive,
+        constraint = !dca_vault_b.load()?.is_completed() @ KryptosError::DcaCompleted,
+    )]
+    pub dca_vault_b: AccountLoader<'info, DcaVault>,
+
+    /// Vault B's input token account
+    #[account(
+        mut,
+        constraint = vault_b_input_token.key() == dca_vault_b.load()?.input_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub vault_b_input_token: Account<'info, TokenAccount>,
+
+    /// Where vault B's output goes (its output vault, or the owner's account for Sell)
+    #[account(mut)]
+    pub vault_b_output_destination: Account<'info, TokenAccount>,
+
+    /// Keeper's token account receiving vault A's unmatched input
+    #[account(
+        mut,
+        constraint = keeper_input_token.owner == keeper.key() @ KryptosError::Unauthorized,
+    )]
+    pub keeper_input_token: Account<'info, TokenAccount>,
+
+    /// Keeper's token account sending the external swap result to vault A
+    #[account(
+        mut,
+        constraint = keeper_output_token.owner == keeper.key() @ KryptosError::Unauthorized,
+    )]
+    pub keeper_output_token: Account<'info, TokenAccount>,
+
+    /// CHECK: Pyth price update for vault A's input token, validated by
+    /// `oracle::read_price`
+    pub input_price_update: UncheckedAccount<'info>,
+
+    /// CHECK: Pyth price update for vault A's output token, validated by
+    /// `oracle::read_price`
+    pub output_price_update: UncheckedAccount<'info>,
+
+    /// Stats shard of vault A's input mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            vault_a_input_token.mint.as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats_a: Account<'info, ProtocolStats>,
+
+    /// Stats shard of vault B's input mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            vault_b_input_token.mint.as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats_b: Account<'info, ProtocolStats>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Settle two opposing vaults against each other at the oracle price,
+/// routing only vault A's unmatched remainder through the keeper
+pub(crate) fn handler(ctx: Context<MatchDca>, params: MatchDcaParams) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Work from copies: both vaults sign transfers below, which needs their
+    // data unborrowed until the executions are booked
+    let dca_vault_a = *ctx.accounts.dca_vault_a.load()?;
+    let dca_vault_b = *ctx.accounts.dca_vault_b.load()?;
+
+    // Check if execution is allowed (time-based)
+    require!(
+        dca_vault_a.can_execute(current_time) && dca_vault_b.can_execute(current_time),
+        KryptosError::DcaExecutionNotAllowed
+    );
+
+    // Vaults must trade opposite directions of one pair, priced by the same feeds
+    require!(
+        dca_vault_a.can_match(&dca_vault_b),
+        KryptosError::UnmatchableVaults
+    );
+    check_output_destination(&dca_vault_a, &ctx.accounts.vault_a_output_destination)?;
+    check_output_destination(&dca_vault_b, &ctx.accounts.vault_b_output_destination)?;
+
+    // Reference prices for both tokens
+    let price_a_input = oracle::read_price(
+        &ctx.accounts.input_price_update.to_account_info(),
+        &dca_vault_a.input_feed_id,
+        current_time,
+    )?;
+    let price_a_output = oracle::read_price(
+        &ctx.accounts.output_price_update.to_account_info(),
+        &dca_vault_a.output_feed_id,
+        current_time,
+    )?;
+
+    // Matching never bypasses a vault's price guard
+    require!(
+        dca_vault_a
+            .check_price_guard(dca_vault_a.guarded_price(price_a_input, price_a_output))
+            .is_none()
+            && dca_vault_b
+                .check_price_guard(dca_vault_b.guarded_price(price_a_output, price_a_input))
+                .is_none(),
+        KryptosError::DcaExecutionNotAllowed
+    );
+
+    // Validate amounts
+    require!(
+        params.matched_a > 0 && params.matched_b > 0,
+        KryptosError::InvalidAmount
+    );
+    require!(
+        (params.external_swap_amount == 0) == (params.external_received_amount == 0),
+        KryptosError::InvalidAmount
+    );
+    let spent_a = params
+        .matched_a
+        .checked_add(params.external_swap_amount)
+        .ok_or(KryptosError::MathOverflow)?;
+    let received_a = params
+        .matched_b
+        .checked_add(params.external_received_amount)
+        .ok_or(KryptosError::MathOverflow)?;
+    require!(
+        spent_a <= dca_vault_a.total_amount.saturating_sub(dca_vault_a.total_spent),
+        KryptosError::InvalidAmount
+    );
+    require!(
+        params.matched_b <= dca_vault_b.total_amount.saturating_sub(dca_vault_b.total_spent),
+        KryptosError::InvalidAmount
+    );
+
+    // Both sides of the match stay within their owner's slippage limit
+    let matched_a_value = dca_vault_a
+        .input_value(params.matched_a, price_a_input)
+        .ok_or(KryptosError::MathOverflow)?;
+    let matched_b_value = dca_vault_b
+        .input_value(params.matched_b, price_a_output)
+        .ok_or(KryptosError::MathOverflow)?;
+    require!(
+        dca_vault_a.within_slippage(matched_a_value, matched_b_value)
+            && dca_vault_b.within_slippage(matched_b_value, matched_a_value),
+        KryptosError::SlippageExceeded
+    );
+
+    // So does vault A's external remainder
+    if params.external_swap_amount > 0 {
+        let external_spent_value = dca_vault_a
+            .input_value(params.external_swap_amount, price_a_input)
+            .ok_or(KryptosError::MathOverflow)?;
+        let external_received_value = dca_vault_a
+            .output_value(params.external_received_amount, price_a_output)
+            .ok_or(KryptosError::MathOverflow)?;
+        require!(
+            dca_vault_a.within_slippage(external_spent_value, external_received_value),
+            KryptosError::SlippageExceeded
+        );
+    }
+
+    // Validate vaults and keeper have the funds
+    require!(
+        ctx.accounts.vault_a_input_token.amount >= spent_a,
+        KryptosError::InsufficientFunds
+    );
+    require!(
+        ctx.accounts.vault_b_input_token.amount >= params.matched_b,
+        KryptosError::InsufficientFunds
+    );
+    require!(
+        ctx.accounts.keeper_output_token.amount >= params.external_received_amount,
+        KryptosError::InsufficientFunds
+    );
+
+    // Prepare PDA signer seeds
+    let authority_a = dca_vault_a.authority;
+    let input_mint_a = dca_vault_a.input_mint;
+    let output_mint_a = dca_vault_a.output_mint;
+    let bump_a = [dca_vault_a.bump];
+    let seeds_a = &[
+        DcaVault::SEED_PREFIX,
+        authority_a.as_ref(),
+        input_mint_a.as_ref(),
+        output_mint_a.as_ref(),
+        &bump_a,
+    ];
+    let signer_seeds_a = &[&seeds_a[..]];
+
+    let authority_b = dca_vault_b.authority;
+    let input_mint_b = dca_vault_b.input_mint;
+    let output_mint_b = dca_vault_b.output_mint;
+    let bump_b = [dca_vault_b.bump];
+    let seeds_b = &[
+        DcaVault::SEED_PREFIX,
+        authority_b.as_ref(),
+        input_mint_b.as_ref(),
+        output_mint_b.as_ref(),
+        &bump_b,
+    ];
+    let signer_seeds_b = &[&seeds_b[..]];
+
+    // 1. Transfer vault A's matched input to vault B's output
+    let transfer_a_to_b = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault_a_input_token.to_account_info(),
+            to: ctx.accounts.vault_b_output_destination.to_account_info(),
+            authority: ctx.accounts.dca_vault_a.to_account_info(),
+        },
+        signer_seeds_a,
+    );
+    transfer(transfer_a_to_b, params.matched_a)?;
+
+    // 2. Transfer vault B's matched input to vault A's output
+    let transfer_b_to_a = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault_b_input_token.to_account_info(),
+            to: ctx.accounts.vault_a_output_destination.to_account_info(),
+            authority: ctx.accounts.dca_vault_b.to_account_info(),
+        },
+        signer_seeds_b,
+    );
+    transfer(transfer_b_to_a, params.matched_b)?;
+
+    // 3. Route vault A's net imbalance through the keeper
+    if params.external_swap_amount > 0 {
+        let transfer_to_keeper = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_a_input_token.to_account_info(),
+                to: ctx.accounts.keeper_input_token.to_account_info(),
+                authority: ctx.accounts.dca_vault_a.to_account_info(),
+            },
+            signer_seeds_a,
+        );
+        transfer(transfer_to_keeper, params.external_swap_amount)?;
+
+        let transfer_to_vault = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.keeper_output_token.to_account_info(),
+                to: ctx.accounts.vault_a_output_destination.to_account_info(),
+                authority: ctx.accounts.keeper.to_account_info(),
+            },
+        );
+        transfer(transfer_to_vault, params.external_received_amount)?;
+    }
+
+    // Book the execution on both vaults
+    let (mint_a, mint_b) = (dca_vault_a.input_mint, dca_vault_b.input_mint);
+    ctx.accounts.protocol_stats_a.init_if_empty(mint_a, ctx.bumps.protocol_stats_a);
+    ctx.accounts.protocol_stats_b.init_if_empty(mint_b, ctx.bumps.protocol_stats_b);
+    record_execution(
+        &mut *ctx.accounts.dca_vault_a.load_mut()?,
+        ctx.accounts.dca_vault_a.key(),
+        &mut ctx.accounts.protocol_stats_a,
+        spent_a,
+        received_a,
+        current_time,
+    )?;
+    record_execution(
+        &mut *ctx.accounts.dca_vault_b.load_mut()?,
+        ctx.accounts.dca_vault_b.key(),
+        &mut ctx.accounts.protocol_stats_b,
+        params.matched_b,
+        params.matched_a,
+        current_time,
+    )?;
+
+    // The match reveals both sides, so the stricter privacy level applies
+    let privacy = dca_vault_a.privacy_level().stricter(dca_vault_b.privacy_level());
+
+    emit!(DcaMatched {
+        vault_a: ctx.accounts.dca_vault_a.key(),
+        vault_b: ctx.accounts.dca_vault_b.key(),
+        matched_a: privacy.event_amount(params.matched_a),
+        matched_b: privacy.event_amount(params.matched_b),
+        external_spent: privacy.event_amount(params.external_swap_amount),
+        external_received: privacy.event_amount(params.external_received_amount),
+        executed_at: current_time,
+    });
+
+    if privacy.logs_enabled() {
+        msg!("DCA vaults matched");
+        msg!("A -> B: {} | B -> A: {}", params.matched_a, params.matched_b);
+    }
+
+    Ok(())
+}
+
+/// Check a vault's output lands in its output vault (Buy) or its owner's
+/// account (Sell)
+fn check_output_destination(dca_vault: &DcaVault, destination: &Account<TokenAccount>) -> Result<()> {
+    match dca_vault.side() {
+        DcaSide::Buy => require_keys_eq!(
+            destination.key(),
+            dca_vault.output_vault,
+            KryptosError::TokenAccountMismatch
+        ),
+        DcaSide::Sell => {
+            require_keys_eq!(destination.owner, dca_vault.authority, KryptosError::Unauthorized);
+            require_keys_eq!(destination.mint, dca_vault.output_mint, KryptosError::InvalidMint);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::state::{DcaVault, IntentVault};
+use crate::errors::KryptosError;
+use crate::events::VaultMigrated;
+
+// ============================================
+// MIGRATE DCA VAULT
+// ============================================
+
+#[derive(Accounts)]
+pub struct MigrateDcaVault<'info> {
+    /// Pays the rent for the grown account (anyone may migrate a vault)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: DcaVault in an earlier Borsh layout, which `AccountLoader`
+    /// cannot decode; validated by `DcaVault::from_legacy`
+    #[account(mut, owner = crate::ID)]
+    pub dca_vault: UncheckedAccount<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_migrate_dca_vault(ctx: Context<MigrateDcaVault>) -> Result<()> {
+    let vault_info = ctx.accounts.dca_vault.to_account_info();
+    let dca_vault = DcaVault::from_legacy(&vault_info.try_borrow_data()?)?;
+
+    // Rewrite the account in the zero-copy layout
+    grow_account(
+        &vault_info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        DcaVault::SPACE,
+    )?;
+    vault_info.try_borrow_mut_data()?[8..DcaVault::SPACE]
+        .copy_from_slice(bytemuck::bytes_of(&dca_vault));
+
+    let clock = Clock::get()?;
+    emit!(VaultMigrated {
+        vault: vault_info.key(),
+        version: dca_vault.version,
+        space: DcaVault::SPACE as u32,
+        migrated_at: clock.unix_timestamp,
+    });
+
+    if dca_vault.privacy_level().logs_enabled() {
+        msg!("DCA vault migrated to layout v{}", dca_vault.version);
+    }
+
+    Ok(())
+}
+
+// ============================================
+// MIGRATE INTENT VAULT
+// ============================================
+
+#[derive(Accounts)]
+pub struct MigrateIntentVault<'info> {
+    /// Pays the rent for the grown account (anyone may migrate a vault)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: IntentVault in any earlier layout, which `Account` cannot
+    /// decode; the discriminator is checked before the account is grown
+    #[account(mut, owner = crate::ID)]
+    pub intent_vault: UncheckedAccount<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_migrate_intent_vault(ctx: Context<MigrateIntentVault>) -> Result<()> {
+    let vault_info = ctx.accounts.intent_vault.to_account_info();
+    require!(
+        vault_info.try_borrow_data()?.starts_with(IntentVault::DISCRIMINATOR),
+        KryptosError::InvalidMigrationAccount
+    );
+
+    grow_account(
+        &vault_info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        IntentVault::SPACE,
+    )?;
+    let intent_vault = IntentVault::migrate(&mut vault_info.try_borrow_mut_data()?)?;
+
+    let clock = Clock::get()?;
+    emit!(VaultMigrated {
+        vault: vault_info.key(),
+        version: intent_vault.version,
+        space: IntentVault::SPACE as u32,
+        migrated_at: clock.unix_timestamp,
+    });
+
+    if intent_vault.privacy_level.logs_enabled() {
+        msg!("Intent vault migrated to layout v{}", intent_vault.version);
+    }
+
+    Ok(())
+}
+
+/// Top up rent and grow `account` to `space` bytes, zero-filling the tail
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?.minimum_balance(space);
+    let shortfall = rent.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        let transfer_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+        transfer(transfer_ctx, shortfall)?;
+    }
+
+    account.resize(space)?;
+    Ok(())
+}
+
+/// Shrink `account` to `space` bytes and refund the rent it no longer needs
+pub(crate) fn shrink_account<'info>(
+    account: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() <= space {
+        return Ok(());
+    }
+
+    account.resize(space)?;
+    let surplus = account
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(space));
+    if surplus > 0 {
+        account.sub_lamports(surplus)?;
+        recipient.add_lamports(surplus)?;
+    }
+    Ok(())
+}
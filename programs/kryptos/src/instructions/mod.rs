@@ -1,14 +1,38 @@
 pub mod initialize_dca;
 pub mod execute_dca;
+pub mod execute_dca_batch;
+pub mod match_dca;
+pub mod basket_dca;
+pub mod dca_pool;
 pub mod create_intent;
 pub mod execute_intent;
+pub mod update_trailing_intent;
+pub mod rebalance;
+pub mod stream;
+pub mod drop;
+pub mod burn_with_receipt;
+pub mod vault_metadata;
 pub mod withdraw;
 pub mod close;
+pub mod migrate;
+pub mod protocol_stats;
 
 // Re-export all structs and Anchor-generated modules
 pub use initialize_dca::*;
 pub use execute_dca::*;
+pub use execute_dca_batch::*;
+pub use match_dca::*;
+pub use basket_dca::*;
+pub use dca_pool::*;
 pub use create_intent::*;
 pub use execute_intent::*;
+pub use update_trailing_intent::*;
+pub use rebalance::*;
+pub use stream::*;
+pub use drop::*;
+pub use burn_with_receipt::*;
+pub use vault_metadata::*;
 pub use withdraw::*;
 pub use close::*;
+pub use migrate::*;
+pub use protocol_stats::*;
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::state::ProtocolStats;
+
+#[derive(Accounts)]
+pub struct InitializeProtocolStats<'info> {
+    /// Pays the rent (anyone may create a shard)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Input mint the shard aggregates
+    pub mint: Account<'info, Mint>,
+
+    /// Stats shard PDA, one per mint
+    #[account(
+        init,
+        payer = payer,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the stats shard of a mint whose vaults all predate shards
+///
+/// `initialize_dca` and `create_intent` create shards on demand; this is
+/// only needed before executing or closing older vaults of a new mint.
+pub(crate) fn handler(ctx: Context<InitializeProtocolStats>) -> Result<()> {
+    let protocol_stats = &mut ctx.accounts.protocol_stats;
+    protocol_stats.init_if_empty(ctx.accounts.mint.key(), ctx.bumps.protocol_stats);
+
+    msg!("Protocol stats initialized for mint {}", protocol_stats.mint);
+
+    Ok(())
+}
@@ -0,0 +1,689 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer, CloseAccount, close_account};
+
+use crate::state::{RebalanceVault, RebalanceAsset, ProtocolStats, MAX_REBALANCE_ASSETS};
+use crate::errors::KryptosError;
+use crate::events::{RebalanceVaultCreated, RebalanceDeposited, PortfolioRebalanced, FundsWithdrawn};
+use crate::oracle;
+
+// ============================================
+// INITIALIZE REBALANCE VAULT
+// ============================================
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RebalanceAssetParams {
+    /// Token to hold
+    pub mint: Pubkey,
+    /// Pyth feed id used to value the token
+    pub feed_id: [u8; 32],
+    /// Target share of the portfolio value (basis points)
+    pub target_weight_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeRebalanceParams {
+    /// Unique nonce to allow multiple portfolios (user-provided)
+    pub nonce: u64,
+    /// Assets and target weights (2-5 distinct mints, weights summing to 10000)
+    pub assets: Vec<RebalanceAssetParams>,
+    /// Minimum drift before rebalancing (basis points, 1-5000)
+    pub drift_threshold_bps: u16,
+    /// Maximum value lost per rebalancing swap (basis points, max 1000)
+    pub max_slippage_bps: u16,
+}
+
+#[derive(Accounts)]
+#[instruction(params: InitializeRebalanceParams)]
+pub struct InitializeRebalance<'info> {
+    /// User creating the portfolio
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Rebalance vault PDA (using nonce for uniqueness)
+    #[account(
+        init,
+        payer = authority,
+        space = RebalanceVault::SPACE,
+        seeds = [
+            RebalanceVault::SEED_PREFIX,
+            authority.key().as_ref(),
+            &params.nonce.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub rebalance_vault: Account<'info, RebalanceVault>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_initialize_rebalance(
+    ctx: Context<InitializeRebalance>,
+    params: InitializeRebalanceParams,
+) -> Result<()> {
+    // Validate config
+    require!(
+        params.drift_threshold_bps > 0 && params.drift_threshold_bps <= 5000,
+        KryptosError::InvalidRebalanceConfig
+    );
+    require!(params.max_slippage_bps <= 1000, KryptosError::InvalidRebalanceConfig);
+
+    // Validate assets: distinct oracle-priced mints with weights summing to 100%
+    require!(
+        params.assets.len() >= 2 && params.assets.len() <= MAX_REBALANCE_ASSETS,
+        KryptosError::InvalidRebalanceAssets
+    );
+    let mut total_weight = 0u32;
+    for (index, asset) in params.assets.iter().enumerate() {
+        require!(
+            asset.target_weight_bps > 0
+                && asset.feed_id != [0u8; 32]
+                && params.assets[..index].iter().all(|other| other.mint != asset.mint),
+            KryptosError::InvalidRebalanceAssets
+        );
+        total_weight += asset.target_weight_bps as u32;
+    }
+    require!(total_weight == 10_000, KryptosError::InvalidRebalanceAssets);
+
+    // Get current timestamp
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Initialize vault; asset token accounts are opened separately
+    let rebalance_vault = &mut ctx.accounts.rebalance_vault;
+    rebalance_vault.authority = ctx.accounts.authority.key();
+    rebalance_vault.nonce = params.nonce;
+    rebalance_vault.num_assets = params.assets.len() as u8;
+    for (slot, asset) in rebalance_vault.assets.iter_mut().zip(params.assets.iter()) {
+        *slot = RebalanceAsset {
+            mint: asset.mint,
+            feed_id: asset.feed_id,
+            target_weight_bps: asset.target_weight_bps,
+            ..RebalanceAsset::default()
+        };
+    }
+    rebalance_vault.drift_threshold_bps = params.drift_threshold_bps;
+    rebalance_vault.max_slippage_bps = params.max_slippage_bps;
+    rebalance_vault.rebalance_count = 0;
+    rebalance_vault.last_rebalance = 0;
+    rebalance_vault.is_active = true;
+    rebalance_vault.created_at = current_time;
+    rebalance_vault.bump = ctx.bumps.rebalance_vault;
+
+    // Emit event
+    emit!(RebalanceVaultCreated {
+        vault: rebalance_vault.key(),
+        authority: rebalance_vault.authority,
+        mints: params.assets.iter().map(|asset| asset.mint).collect(),
+        target_weights_bps: params.assets.iter().map(|asset| asset.target_weight_bps).collect(),
+        drift_threshold_bps: params.drift_threshold_bps,
+        created_at: current_time,
+    });
+
+    msg!("Rebalance vault created successfully");
+    msg!("Vault: {}", rebalance_vault.key());
+    msg!("Assets: {} | Drift threshold: {} bps", rebalance_vault.num_assets, params.drift_threshold_bps);
+
+    Ok(())
+}
+
+// ============================================
+// OPEN REBALANCE ASSET
+// ============================================
+
+#[derive(Accounts)]
+#[instruction(asset_index: u8)]
+pub struct OpenRebalanceAsset<'info> {
+    /// Owner of the portfolio (pays rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Portfolio the asset belongs to
+    #[account(
+        mut,
+        constraint = rebalance_vault.authority == authority.key() @ KryptosError::Unauthorized,
+        constraint = asset_index < rebalance_vault.num_assets @ KryptosError::InvalidRebalanceAssets,
+        constraint = !rebalance_vault.assets[asset_index as usize].is_open()
+            @ KryptosError::RebalanceAssetAlreadyOpen,
+    )]
+    pub rebalance_vault: Account<'info, RebalanceVault>,
+
+    /// Token mint configured at this index
+    #[account(
+        constraint = asset_mint.key() == rebalance_vault.assets[asset_index as usize].mint
+            @ KryptosError::InvalidMint,
+    )]
+    pub asset_mint: Account<'info, Mint>,
+
+    /// Vault's token account for this index
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            RebalanceVault::ASSET_SEED_PREFIX,
+            rebalance_vault.key().as_ref(),
+            &[asset_index],
+        ],
+        bump,
+        token::mint = asset_mint,
+        token::authority = rebalance_vault,
+    )]
+    pub vault_asset_token: Account<'info, TokenAccount>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler_open_rebalance_asset(ctx: Context<OpenRebalanceAsset>, asset_index: u8) -> Result<()> {
+    let rebalance_vault = &mut ctx.accounts.rebalance_vault;
+    let asset = &mut rebalance_vault.assets[asset_index as usize];
+    asset.vault = ctx.accounts.vault_asset_token.key();
+    asset.decimals = ctx.accounts.asset_mint.decimals;
+    asset.vault_bump = ctx.bumps.vault_asset_token;
+
+    msg!("Rebalance asset {} opened: {}", asset_index, asset.vault);
+
+    Ok(())
+}
+
+// ============================================
+// DEPOSIT INTO REBALANCE VAULT
+// ============================================
+
+#[derive(Accounts)]
+#[instruction(asset_index: u8)]
+pub struct DepositRebalance<'info> {
+    /// Owner of the portfolio
+    pub authority: Signer<'info>,
+
+    /// Portfolio to deposit into
+    #[account(
+        constraint = rebalance_vault.authority == authority.key() @ KryptosError::Unauthorized,
+        constraint = rebalance_vault.is_active @ KryptosError::RebalanceNotActive,
+        constraint = asset_index < rebalance_vault.num_assets @ KryptosError::InvalidRebalanceAssets,
+    )]
+    pub rebalance_vault: Account<'info, RebalanceVault>,
+
+    /// Vault's token account for the deposited asset
+    #[account(
+        mut,
+        constraint = vault_asset_token.key() == rebalance_vault.assets[asset_index as usize].vault
+            @ KryptosError::TokenAccountMismatch,
+    )]
+    pub vault_asset_token: Account<'info, TokenAccount>,
+
+    /// User's token account (source of funds)
+    #[account(
+        mut,
+        constraint = user_asset_token.owner == authority.key() @ KryptosError::Unauthorized,
+    )]
+    pub user_asset_token: Account<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_deposit_rebalance(
+    ctx: Context<DepositRebalance>,
+    asset_index: u8,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, KryptosError::InvalidAmount);
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Transfer tokens from user to vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_asset_token.to_account_info(),
+            to: ctx.accounts.vault_asset_token.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, amount)?;
+
+    let rebalance_vault = &ctx.accounts.rebalance_vault;
+    emit!(RebalanceDeposited {
+        vault: rebalance_vault.key(),
+        authority: rebalance_vault.authority,
+        mint: rebalance_vault.assets[asset_index as usize].mint,
+        amount,
+        deposited_at: current_time,
+    });
+
+    msg!("Deposited {} into asset {}", amount, asset_index);
+
+    Ok(())
+}
+
+// ============================================
+// REBALANCE PORTFOLIO
+// ============================================
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RebalanceParams {
+    /// Overweight asset to sell
+    pub sell_index: u8,
+    /// Underweight asset to buy
+    pub buy_index: u8,
+    /// Amount of the sold asset swapped by the keeper
+    pub sell_amount: u64,
+    /// Amount of the bought asset received from the swap
+    pub received_amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct Rebalance<'info> {
+    /// Keeper/crank that triggers execution
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Portfolio to rebalance
+    #[account(
+        mut,
+        constraint = rebalance_vault.is_active @ KryptosError::RebalanceNotActive,
+    )]
+    pub rebalance_vault: Account<'info, RebalanceVault>,
+
+    /// Vault's token account of the sold asset
+    #[account(mut)]
+    pub vault_sell_token: Account<'info, TokenAccount>,
+
+    /// Vault's token account of the bought asset
+    #[account(mut)]
+    pub vault_buy_token: Account<'info, TokenAccount>,
+
+    /// Keeper's token account receiving the sold asset for the swap
+    #[account(
+        mut,
+        constraint = keeper_input_token.owner == keeper.key() @ KryptosError::Unauthorized,
+    )]
+    pub keeper_input_token: Account<'info, TokenAccount>,
+
+    /// Keeper's token account sending the bought asset to the vault
+    #[account(
+        mut,
+        constraint = keeper_output_token.owner == keeper.key() @ KryptosError::Unauthorized,
+    )]
+    pub keeper_output_token: Account<'info, TokenAccount>,
+
+    /// Stats shard of the sold asset's mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = ProtocolStats::SPACE,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            vault_sell_token.mint.as_ref(),
+        ],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: (vault asset token, Pyth price update) for every
+    // asset, in asset order
+}
+
+pub fn handler_rebalance<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>,
+    params: RebalanceParams,
+) -> Result<()> {
+    let rebalance_vault = &mut ctx.accounts.rebalance_vault;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Validate the traded pair
+    let sell_index = params.sell_index as usize;
+    let buy_index = params.buy_index as usize;
+    let num_assets = rebalance_vault.num_assets as usize;
+    require!(
+        sell_index < num_assets && buy_index < num_assets && sell_index != buy_index,
+        KryptosError::InvalidRebalanceTrade
+    );
+    require!(params.sell_amount > 0, KryptosError::InvalidAmount);
+    require!(params.received_amount > 0, KryptosError::InvalidAmount);
+    require_keys_eq!(
+        ctx.accounts.vault_sell_token.key(),
+        rebalance_vault.assets[sell_index].vault,
+        KryptosError::TokenAccountMismatch
+    );
+    require_keys_eq!(
+        ctx.accounts.vault_buy_token.key(),
+        rebalance_vault.assets[buy_index].vault,
+        KryptosError::TokenAccountMismatch
+    );
+
+    // Value every asset at verified oracle prices
+    require!(
+        ctx.remaining_accounts.len() == num_assets * 2,
+        KryptosError::TokenAccountMismatch
+    );
+    let mut prices = [0u64; MAX_REBALANCE_ASSETS];
+    let mut values = [0u64; MAX_REBALANCE_ASSETS];
+    for (index, pair) in ctx.remaining_accounts.chunks(2).enumerate() {
+        let asset = rebalance_vault.assets[index];
+        require!(asset.is_open(), KryptosError::RebalanceAssetNotOpen);
+
+        let vault_asset_token = Account::<TokenAccount>::try_from(&pair[0])?;
+        require_keys_eq!(
+            vault_asset_token.key(),
+            asset.vault,
+            KryptosError::TokenAccountMismatch
+        );
+
+        prices[index] = oracle::read_price(&pair[1], &asset.feed_id, current_time)?;
+        values[index] = asset
+            .value_of(vault_asset_token.amount, prices[index])
+            .ok_or(KryptosError::MathOverflow)?;
+    }
+    let values = &values[..num_assets];
+
+    // Only rebalance once some asset drifted past the threshold
+    let max_drift_bps = rebalance_vault.max_drift_bps(values);
+    require!(
+        max_drift_bps >= rebalance_vault.drift_threshold_bps as u64,
+        KryptosError::DriftBelowThreshold
+    );
+
+    // Sell an overweight asset into an underweight one, without overshooting
+    // either target
+    let total_value = values.iter().try_fold(0u64, |total, value| total.checked_add(*value))
+        .ok_or(KryptosError::MathOverflow)?;
+    let sell_excess = values[sell_index]
+        .saturating_sub(rebalance_vault.target_value(sell_index, total_value));
+    let buy_deficit = rebalance_vault
+        .target_value(buy_index, total_value)
+        .saturating_sub(values[buy_index]);
+
+    let value_sold = rebalance_vault.assets[sell_index]
+        .value_of(params.sell_amount, prices[sell_index])
+        .ok_or(KryptosError::MathOverflow)?;
+    require!(
+        value_sold > 0 && value_sold <= sell_excess.min(buy_deficit),
+        KryptosError::InvalidRebalanceTrade
+    );
+
+    // The keeper must return the sold value minus at most the allowed slippage
+    let value_bought = rebalance_vault.assets[buy_index]
+        .value_of(params.received_amount, prices[buy_index])
+        .ok_or(KryptosError::MathOverflow)?;
+    require!(
+        value_bought as u128 * 10_000
+            >= value_sold as u128 * (10_000 - rebalance_vault.max_slippage_bps as u128),
+        KryptosError::SlippageExceeded
+    );
+
+    // Validate vault has enough funds
+    require!(
+        ctx.accounts.vault_sell_token.amount >= params.sell_amount,
+        KryptosError::InsufficientFunds
+    );
+
+    // Validate keeper has the bought tokens ready
+    require!(
+        ctx.accounts.keeper_output_token.amount >= params.received_amount,
+        KryptosError::InsufficientFunds
+    );
+
+    // Prepare PDA signer seeds
+    let authority_key = rebalance_vault.authority;
+    let nonce_bytes = rebalance_vault.nonce.to_le_bytes();
+    let bump = rebalance_vault.bump;
+
+    let seeds = &[
+        RebalanceVault::SEED_PREFIX,
+        authority_key.as_ref(),
+        &nonce_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // 1. Transfer the sold asset from vault to keeper
+    let transfer_to_keeper = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault_sell_token.to_account_info(),
+            to: ctx.accounts.keeper_input_token.to_account_info(),
+            authority: rebalance_vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer(transfer_to_keeper, params.sell_amount)?;
+
+    // 2. Transfer the bought asset from keeper to vault
+    let transfer_to_vault = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.keeper_output_token.to_account_info(),
+            to: ctx.accounts.vault_buy_token.to_account_info(),
+            authority: ctx.accounts.keeper.to_account_info(),
+        },
+    );
+    transfer(transfer_to_vault, params.received_amount)?;
+
+    // Update vault state
+    rebalance_vault.rebalance_count = rebalance_vault
+        .rebalance_count
+        .checked_add(1)
+        .ok_or(KryptosError::MathOverflow)?;
+    rebalance_vault.last_rebalance = current_time;
+
+    // Book the trade on the sold asset's stats shard
+    let protocol_stats = &mut ctx.accounts.protocol_stats;
+    protocol_stats.init_if_empty(ctx.accounts.vault_sell_token.mint, ctx.bumps.protocol_stats);
+    protocol_stats.record_execution(params.sell_amount);
+
+    // Emit event
+    emit!(PortfolioRebalanced {
+        vault: rebalance_vault.key(),
+        authority: rebalance_vault.authority,
+        sell_mint: rebalance_vault.assets[sell_index].mint,
+        buy_mint: rebalance_vault.assets[buy_index].mint,
+        amount_sold: params.sell_amount,
+        amount_bought: params.received_amount,
+        value_sold,
+        value_bought,
+        max_drift_bps: max_drift_bps as u16,
+        rebalanced_at: current_time,
+    });
+
+    msg!("Portfolio rebalanced successfully");
+    msg!("Sold: {} | Bought: {}", params.sell_amount, params.received_amount);
+    msg!("Drift before: {} bps", max_drift_bps);
+
+    Ok(())
+}
+
+// ============================================
+// WITHDRAW FROM REBALANCE VAULT
+// ============================================
+
+#[derive(Accounts)]
+pub struct WithdrawRebalance<'info> {
+    /// Owner of the portfolio
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Portfolio to withdraw from
+    #[account(
+        mut,
+        constraint = rebalance_vault.authority == authority.key() @ KryptosError::Unauthorized,
+    )]
+    pub rebalance_vault: Account<'info, RebalanceVault>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: (vault asset token, user token) pairs for the
+    // assets to withdraw
+}
+
+pub fn handler_withdraw_rebalance<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawRebalance<'info>>,
+) -> Result<()> {
+    let rebalance_vault = &mut ctx.accounts.rebalance_vault;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        ctx.remaining_accounts.len() % 2 == 0,
+        KryptosError::TokenAccountMismatch
+    );
+
+    // Prepare PDA signer seeds
+    let authority_key = rebalance_vault.authority;
+    let nonce_bytes = rebalance_vault.nonce.to_le_bytes();
+    let bump = rebalance_vault.bump;
+
+    let seeds = &[
+        RebalanceVault::SEED_PREFIX,
+        authority_key.as_ref(),
+        &nonce_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Transfer the balance of every listed asset to user
+    let mut withdrawn = 0u64;
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let vault_asset_token = Account::<TokenAccount>::try_from(&pair[0])?;
+        require!(
+            rebalance_vault
+                .active_assets()
+                .iter()
+                .any(|asset| asset.is_open() && asset.vault == vault_asset_token.key()),
+            KryptosError::TokenAccountMismatch
+        );
+
+        let balance = vault_asset_token.amount;
+        if balance > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: pair[0].clone(),
+                    to: pair[1].clone(),
+                    authority: rebalance_vault.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer(transfer_ctx, balance)?;
+
+            withdrawn = withdrawn
+                .checked_add(balance)
+                .ok_or(KryptosError::MathOverflow)?;
+        }
+    }
+
+    // Stop rebalancing
+    rebalance_vault.is_active = false;
+
+    emit!(FundsWithdrawn {
+        vault: rebalance_vault.key(),
+        authority: rebalance_vault.authority,
+        amount: withdrawn,
+        vault_type: "Rebalance".to_string(),
+        withdrawn_at: current_time,
+    });
+
+    msg!("Rebalance vault withdrawn successfully");
+    msg!("Amount returned: {}", withdrawn);
+
+    Ok(())
+}
+
+// ============================================
+// CLOSE REBALANCE VAULT
+// ============================================
+
+#[derive(Accounts)]
+pub struct CloseRebalance<'info> {
+    /// Owner of the portfolio
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Portfolio to close
+    #[account(
+        mut,
+        close = authority,
+        constraint = rebalance_vault.authority == authority.key() @ KryptosError::Unauthorized,
+        constraint = !rebalance_vault.is_active @ KryptosError::RebalanceNotActive,
+    )]
+    pub rebalance_vault: Account<'info, RebalanceVault>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: every opened asset token account (must be empty),
+    // in asset order
+}
+
+pub fn handler_close_rebalance<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseRebalance<'info>>,
+) -> Result<()> {
+    let rebalance_vault = &ctx.accounts.rebalance_vault;
+
+    // Every opened asset account must be closed with the vault
+    let open_assets: Vec<&RebalanceAsset> = rebalance_vault
+        .active_assets()
+        .iter()
+        .filter(|asset| asset.is_open())
+        .collect();
+    require!(
+        ctx.remaining_accounts.len() == open_assets.len(),
+        KryptosError::TokenAccountMismatch
+    );
+
+    // Prepare PDA signer seeds
+    let authority_key = rebalance_vault.authority;
+    let nonce_bytes = rebalance_vault.nonce.to_le_bytes();
+    let bump = rebalance_vault.bump;
+
+    let seeds = &[
+        RebalanceVault::SEED_PREFIX,
+        authority_key.as_ref(),
+        &nonce_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Close asset token accounts
+    for (asset, account) in open_assets.iter().zip(ctx.remaining_accounts.iter()) {
+        let vault_asset_token = Account::<TokenAccount>::try_from(account)?;
+        require_keys_eq!(
+            vault_asset_token.key(),
+            asset.vault,
+            KryptosError::TokenAccountMismatch
+        );
+        require!(
+            vault_asset_token.amount == 0,
+            KryptosError::RebalanceHasRemainingFunds
+        );
+
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: account.clone(),
+                destination: ctx.accounts.authority.to_account_info(),
+                authority: rebalance_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(close_ctx)?;
+    }
+
+    msg!("Rebalance vault closed successfully");
+    msg!("Rent reclaimed by: {}", ctx.accounts.authority.key());
+
+    Ok(())
+}
@@ -0,0 +1,370 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer, CloseAccount, close_account};
+
+use crate::state::StreamVault;
+use crate::errors::KryptosError;
+use crate::events::{StreamCreated, StreamClaimed, StreamCancelled};
+use crate::entropy;
+
+// ============================================
+// CREATE STREAM
+// ============================================
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateStreamParams {
+    /// Unique nonce to allow multiple streams (user-provided)
+    pub nonce: u64,
+    /// Total amount to stream (in smallest unit)
+    pub total_amount: u64,
+    /// Amount vested by each payment
+    pub amount_per_payment: u64,
+    /// Seconds between payments
+    pub interval_seconds: u32,
+    /// Timing variance per payment (basis points of the interval, max 2500 = ±25%)
+    pub variance_bps: u16,
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateStreamParams)]
+pub struct CreateStream<'info> {
+    /// User funding the stream
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: Payment recipient, only stored and used as a PDA seed
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Stream vault PDA (using nonce for uniqueness)
+    #[account(
+        init,
+        payer = sender,
+        space = StreamVault::SPACE,
+        seeds = [
+            StreamVault::SEED_PREFIX,
+            sender.key().as_ref(),
+            recipient.key().as_ref(),
+            &params.nonce.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub stream_vault: Account<'info, StreamVault>,
+
+    /// Token mint being streamed
+    pub mint: Account<'info, Mint>,
+
+    /// Sender's token account (source of funds)
+    #[account(
+        mut,
+        constraint = sender_token.mint == mint.key() @ KryptosError::InvalidMint,
+        constraint = sender_token.owner == sender.key() @ KryptosError::Unauthorized,
+    )]
+    pub sender_token: Account<'info, TokenAccount>,
+
+    /// Vault's token account (holds the unpaid balance)
+    #[account(
+        init,
+        payer = sender,
+        seeds = [
+            b"stream_token_vault",
+            stream_vault.key().as_ref(),
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = stream_vault,
+    )]
+    pub vault_token: Account<'info, TokenAccount>,
+
+    /// CHECK: SlotHashes sysvar, seeds the payment timing jitter
+    #[account(address = entropy::SLOT_HASHES_ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler_create_stream(ctx: Context<CreateStream>, params: CreateStreamParams) -> Result<()> {
+    // Validate parameters
+    require!(params.total_amount > 0, KryptosError::InvalidAmount);
+    require!(
+        params.amount_per_payment > 0 && params.amount_per_payment <= params.total_amount,
+        KryptosError::InvalidAmount
+    );
+    require!(params.interval_seconds > 0, KryptosError::InvalidStreamConfig);
+    require!(
+        params.variance_bps <= StreamVault::MAX_VARIANCE_BPS,
+        KryptosError::InvalidVariance
+    );
+    require_keys_neq!(
+        ctx.accounts.recipient.key(),
+        ctx.accounts.sender.key(),
+        KryptosError::InvalidStreamConfig
+    );
+
+    // Get current timestamp
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Initialize stream vault
+    let stream_vault = &mut ctx.accounts.stream_vault;
+    stream_vault.sender = ctx.accounts.sender.key();
+    stream_vault.recipient = ctx.accounts.recipient.key();
+    stream_vault.nonce = params.nonce;
+    stream_vault.mint = ctx.accounts.mint.key();
+    stream_vault.token_vault = ctx.accounts.vault_token.key();
+    stream_vault.total_amount = params.total_amount;
+    stream_vault.amount_per_payment = params.amount_per_payment;
+    stream_vault.interval_seconds = params.interval_seconds;
+    stream_vault.variance_bps = params.variance_bps;
+    // Payment times cannot be predicted before the creating slot is produced
+    let slot_hash = entropy::recent_slot_hash(&ctx.accounts.slot_hashes)?;
+    stream_vault.jitter_seed =
+        entropy::seed_u64(&entropy::vault_seed(&slot_hash, &stream_vault.key(), 0), 0);
+    stream_vault.start_time = current_time;
+    stream_vault.total_claimed = 0;
+    stream_vault.is_active = true;
+    stream_vault.created_at = current_time;
+    stream_vault.bump = ctx.bumps.stream_vault;
+    stream_vault.vault_bump = ctx.bumps.vault_token;
+
+    // Transfer tokens from sender to vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.sender_token.to_account_info(),
+            to: ctx.accounts.vault_token.to_account_info(),
+            authority: ctx.accounts.sender.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, params.total_amount)?;
+
+    // Emit event
+    emit!(StreamCreated {
+        stream: stream_vault.key(),
+        sender: stream_vault.sender,
+        recipient: stream_vault.recipient,
+        mint: stream_vault.mint,
+        total_amount: stream_vault.total_amount,
+        amount_per_payment: stream_vault.amount_per_payment,
+        interval_seconds: stream_vault.interval_seconds,
+        created_at: current_time,
+    });
+
+    msg!("Stream created successfully");
+    msg!("Stream: {}", stream_vault.key());
+    msg!("Payments: {} x {}", stream_vault.num_payments(), params.amount_per_payment);
+
+    Ok(())
+}
+
+// ============================================
+// CLAIM STREAM
+// ============================================
+
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+    /// Recipient of the stream
+    pub recipient: Signer<'info>,
+
+    /// Stream to claim from
+    #[account(
+        mut,
+        constraint = stream_vault.recipient == recipient.key() @ KryptosError::Unauthorized,
+        constraint = stream_vault.is_active @ KryptosError::StreamNotActive,
+    )]
+    pub stream_vault: Account<'info, StreamVault>,
+
+    /// Vault's token account
+    #[account(
+        mut,
+        constraint = vault_token.key() == stream_vault.token_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub vault_token: Account<'info, TokenAccount>,
+
+    /// Recipient's token account (receives vested payments)
+    #[account(mut)]
+    pub recipient_token: Account<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+    let stream_vault = &mut ctx.accounts.stream_vault;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let claimable = stream_vault.claimable_amount(current_time);
+    require!(claimable > 0, KryptosError::NothingToClaim);
+
+    // Prepare PDA signer seeds
+    let sender_key = stream_vault.sender;
+    let recipient_key = stream_vault.recipient;
+    let nonce_bytes = stream_vault.nonce.to_le_bytes();
+    let bump = stream_vault.bump;
+
+    let seeds = &[
+        StreamVault::SEED_PREFIX,
+        sender_key.as_ref(),
+        recipient_key.as_ref(),
+        &nonce_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Transfer vested tokens to recipient
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault_token.to_account_info(),
+            to: ctx.accounts.recipient_token.to_account_info(),
+            authority: stream_vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer(transfer_ctx, claimable)?;
+
+    stream_vault.total_claimed = stream_vault
+        .total_claimed
+        .checked_add(claimable)
+        .ok_or(KryptosError::MathOverflow)?;
+
+    emit!(StreamClaimed {
+        stream: stream_vault.key(),
+        recipient: stream_vault.recipient,
+        amount: claimable,
+        total_claimed: stream_vault.total_claimed,
+        claimed_at: current_time,
+    });
+
+    msg!("Stream claimed: {}", claimable);
+    msg!("Claimed so far: {}/{}", stream_vault.total_claimed, stream_vault.total_amount);
+
+    Ok(())
+}
+
+// ============================================
+// CANCEL STREAM
+// ============================================
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    /// Sender of the stream (receives unvested funds and rent)
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// Stream to cancel
+    #[account(
+        mut,
+        close = sender,
+        constraint = stream_vault.sender == sender.key() @ KryptosError::Unauthorized,
+    )]
+    pub stream_vault: Account<'info, StreamVault>,
+
+    /// Vault's token account
+    #[account(
+        mut,
+        constraint = vault_token.key() == stream_vault.token_vault @ KryptosError::TokenAccountMismatch,
+    )]
+    pub vault_token: Account<'info, TokenAccount>,
+
+    /// Sender's token account (receives unvested funds)
+    #[account(mut)]
+    pub sender_token: Account<'info, TokenAccount>,
+
+    /// Recipient's token account (receives vested, unclaimed funds)
+    #[account(
+        mut,
+        constraint = recipient_token.owner == stream_vault.recipient @ KryptosError::Unauthorized,
+        constraint = recipient_token.mint == stream_vault.mint @ KryptosError::InvalidMint,
+    )]
+    pub recipient_token: Account<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+    let stream_vault = &ctx.accounts.stream_vault;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Split the balance: vested goes to the recipient, the rest back to sender
+    let balance = ctx.accounts.vault_token.amount;
+    let vested_unclaimed = stream_vault.claimable_amount(current_time).min(balance);
+    let unvested = balance - vested_unclaimed;
+
+    // Prepare PDA signer seeds
+    let sender_key = stream_vault.sender;
+    let recipient_key = stream_vault.recipient;
+    let nonce_bytes = stream_vault.nonce.to_le_bytes();
+    let bump = stream_vault.bump;
+
+    let seeds = &[
+        StreamVault::SEED_PREFIX,
+        sender_key.as_ref(),
+        recipient_key.as_ref(),
+        &nonce_bytes,
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Pay out vested tokens to recipient
+    if vested_unclaimed > 0 {
+        let transfer_recipient_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_token.to_account_info(),
+                to: ctx.accounts.recipient_token.to_account_info(),
+                authority: stream_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_recipient_ctx, vested_unclaimed)?;
+    }
+
+    // Return unvested tokens to sender
+    if unvested > 0 {
+        let transfer_sender_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_token.to_account_info(),
+                to: ctx.accounts.sender_token.to_account_info(),
+                authority: stream_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_sender_ctx, unvested)?;
+    }
+
+    // Close the token account; the stream account is closed by Anchor
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault_token.to_account_info(),
+            destination: ctx.accounts.sender.to_account_info(),
+            authority: stream_vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    close_account(close_ctx)?;
+
+    emit!(StreamCancelled {
+        stream: stream_vault.key(),
+        sender: stream_vault.sender,
+        recipient: stream_vault.recipient,
+        vested_paid: vested_unclaimed,
+        unvested_refunded: unvested,
+        cancelled_at: current_time,
+    });
+
+    msg!("Stream cancelled successfully");
+    msg!("Vested paid: {} | Unvested returned: {}", vested_unclaimed, unvested);
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{IntentVault, IntentStatus, TriggerType};
+use crate::errors::KryptosError;
+use crate::events::TrailingPeakUpdated;
+use crate::instructions::execute_intent::oracle_trigger_price;
+
+#[derive(Accounts)]
+pub struct UpdateTrailingIntent<'info> {
+    /// Trailing stop intent to update (anyone may crank it)
+    #[account(
+        mut,
+        constraint = intent_vault.status == IntentStatus::Monitoring @ KryptosError::IntentNotMonitoring,
+        constraint = matches!(intent_vault.trigger_type, TriggerType::TrailingStop { .. })
+            @ KryptosError::NotTrailingIntent,
+    )]
+    pub intent_vault: Account<'info, IntentVault>,
+
+    /// CHECK: Pyth price update for the input token (Sell/Swap intents),
+    /// validated by `oracle::read_price`
+    pub input_price_update: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Pyth price update for the output token (Buy/Swap intents),
+    /// validated by `oracle::read_price`
+    pub output_price_update: Option<UncheckedAccount<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateTrailingIntent>) -> Result<()> {
+    let intent_vault = &mut ctx.accounts.intent_vault;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Check if expired
+    require!(
+        !intent_vault.is_expired(current_time),
+        KryptosError::IntentExpired
+    );
+
+    // Read verified price for the intent's feeds
+    let current_price = oracle_trigger_price(
+        intent_vault,
+        ctx.accounts.input_price_update.as_ref(),
+        ctx.accounts.output_price_update.as_ref(),
+        current_time,
+    )?;
+
+    // Move the high-water mark if the price made a new peak
+    let previous_peak = intent_vault.peak_price;
+    if intent_vault.observe_price(current_price, current_time) {
+        let privacy = intent_vault.privacy_level;
+        emit!(TrailingPeakUpdated {
+            vault: intent_vault.key(),
+            authority: intent_vault.authority,
+            previous_peak: privacy.event_amount(previous_peak),
+            new_peak: privacy.event_amount(intent_vault.peak_price),
+            stop_price: privacy.event_amount(intent_vault.trailing_stop_price()),
+            updated_at: current_time,
+        });
+
+        if privacy.logs_enabled() {
+            msg!("Trailing peak updated: {} -> {}", previous_peak, intent_vault.peak_price);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{DcaVault, IntentVault, VaultMetadata, MAX_METADATA_LEN};
+use crate::errors::KryptosError;
+use crate::events::VaultMetadataUpdated;
+
+// ============================================
+// INITIALIZE VAULT METADATA
+// ============================================
+
+#[derive(Accounts)]
+#[instruction(len: u32)]
+pub struct InitializeVaultMetadata<'info> {
+    /// Vault owner
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: DcaVault or IntentVault owned by this program, validated by
+    /// `vault_authority`
+    pub vault: UncheckedAccount<'info>,
+
+    /// Metadata PDA, one per vault
+    #[account(
+        init,
+        payer = authority,
+        space = VaultMetadata::space(len as usize),
+        seeds = [
+            VaultMetadata::SEED_PREFIX,
+            vault.key().as_ref(),
+        ],
+        bump
+    )]
+    pub metadata: Account<'info, VaultMetadata>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_initialize_vault_metadata(
+    ctx: Context<InitializeVaultMetadata>,
+    len: u32,
+) -> Result<()> {
+    require!(len as usize <= MAX_METADATA_LEN, KryptosError::InvalidMetadata);
+
+    // Only the vault owner may attach metadata
+    let authority = vault_authority(&ctx.accounts.vault)?;
+    require_keys_eq!(
+        authority,
+        ctx.accounts.authority.key(),
+        KryptosError::Unauthorized
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.vault = ctx.accounts.vault.key();
+    metadata.authority = authority;
+    metadata.updated_at = current_time;
+    metadata.bump = ctx.bumps.metadata;
+    metadata.data = vec![0u8; len as usize];
+
+    emit!(VaultMetadataUpdated {
+        metadata: metadata.key(),
+        vault: metadata.vault,
+        authority,
+        len,
+        updated_at: current_time,
+    });
+
+    Ok(())
+}
+
+// ============================================
+// WRITE VAULT METADATA
+// ============================================
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct WriteVaultMetadataParams {
+    /// Blob size after the write (the account is resized to fit)
+    pub len: u32,
+    /// Position of `bytes` in the blob
+    pub offset: u32,
+    /// Encrypted bytes to write; large blobs are written in several chunks
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: WriteVaultMetadataParams)]
+pub struct WriteVaultMetadata<'info> {
+    /// Vault owner (pays for growth, receives rent from shrinking)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Metadata to write
+    #[account(
+        mut,
+        has_one = authority @ KryptosError::Unauthorized,
+        realloc = VaultMetadata::space(params.len as usize),
+        realloc::payer = authority,
+        realloc::zero = true,
+    )]
+    pub metadata: Account<'info, VaultMetadata>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_write_vault_metadata(
+    ctx: Context<WriteVaultMetadata>,
+    params: WriteVaultMetadataParams,
+) -> Result<()> {
+    let len = params.len as usize;
+    let offset = params.offset as usize;
+    let end = offset
+        .checked_add(params.bytes.len())
+        .ok_or(KryptosError::MathOverflow)?;
+    require!(
+        len <= MAX_METADATA_LEN && end <= len,
+        KryptosError::InvalidMetadata
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.data.resize(len, 0);
+    metadata.data[offset..end].copy_from_slice(&params.bytes);
+    metadata.updated_at = current_time;
+
+    emit!(VaultMetadataUpdated {
+        metadata: metadata.key(),
+        vault: metadata.vault,
+        authority: metadata.authority,
+        len: params.len,
+        updated_at: current_time,
+    });
+
+    Ok(())
+}
+
+// ============================================
+// CLOSE VAULT METADATA
+// ============================================
+
+#[derive(Accounts)]
+pub struct CloseVaultMetadata<'info> {
+    /// Vault owner (receives rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Metadata to close
+    #[account(
+        mut,
+        has_one = authority @ KryptosError::Unauthorized,
+        close = authority,
+    )]
+    pub metadata: Account<'info, VaultMetadata>,
+}
+
+pub fn handler_close_vault_metadata(ctx: Context<CloseVaultMetadata>) -> Result<()> {
+    msg!("Vault metadata closed");
+    msg!("Rent reclaimed by: {}", ctx.accounts.authority.key());
+
+    Ok(())
+}
+
+/// Owner of a DcaVault or IntentVault
+fn vault_authority(vault: &UncheckedAccount) -> Result<Pubkey> {
+    require_keys_eq!(*vault.owner, crate::ID, KryptosError::InvalidMetadataVault);
+
+    let data = vault.try_borrow_data()?;
+    if data.starts_with(DcaVault::DISCRIMINATOR) && data.len() >= DcaVault::SPACE {
+        let dca_vault: DcaVault = bytemuck::pod_read_unaligned(&data[8..DcaVault::SPACE]);
+        return Ok(dca_vault.authority);
+    }
+    let intent_vault = IntentVault::try_deserialize(&mut &data[..])
+        .map_err(|_| KryptosError::InvalidMetadataVault)?;
+    Ok(intent_vault.authority)
+}
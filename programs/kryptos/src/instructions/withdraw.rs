@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
 
-use crate::state::{DcaVault, IntentVault, IntentStatus};
+use crate::state::{DcaVault, IntentVault, IntentStatus, ProtocolStats};
 use crate::errors::KryptosError;
-use crate::events::{DcaCancelled, IntentCancelled, FundsWithdrawn};
+use crate::events::{DcaCancelled, DcaOutputClaimed, IntentCancelled, FundsWithdrawn};
 
 // ============================================
 // WITHDRAW FROM DCA VAULT
@@ -18,21 +18,21 @@ pub struct WithdrawDca<'info> {
     /// DCA vault to withdraw from
     #[account(
         mut,
-        constraint = dca_vault.authority == authority.key() @ KryptosError::Unauthorized,
+        constraint = dca_vault.load()?.authority == authority.key() @ KryptosError::Unauthorized,
     )]
-    pub dca_vault: Account<'info, DcaVault>,
+    pub dca_vault: AccountLoader<'info, DcaVault>,
 
     /// Vault's input token account
     #[account(
         mut,
-        constraint = vault_input_token.key() == dca_vault.input_vault @ KryptosError::TokenAccountMismatch,
+        constraint = vault_input_token.key() == dca_vault.load()?.input_vault @ KryptosError::TokenAccountMismatch,
     )]
     pub vault_input_token: Account<'info, TokenAccount>,
 
     /// Vault's output token account
     #[account(
         mut,
-        constraint = vault_output_token.key() == dca_vault.output_vault @ KryptosError::TokenAccountMismatch,
+        constraint = vault_output_token.key() == dca_vault.load()?.output_vault @ KryptosError::TokenAccountMismatch,
     )]
     pub vault_output_token: Account<'info, TokenAccount>,
 
@@ -44,12 +44,25 @@ pub struct WithdrawDca<'info> {
     #[account(mut)]
     pub user_output_token: Account<'info, TokenAccount>,
 
+    /// Stats shard of the vault's input mint (may be omitted unless the
+    /// vault is still counted as active)
+    #[account(
+        mut,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            vault_input_token.mint.as_ref(),
+        ],
+        bump = protocol_stats.bump,
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
     /// Token program
     pub token_program: Program<'info, Token>,
 }
 
 pub fn handler_withdraw_dca(ctx: Context<WithdrawDca>) -> Result<()> {
-    let dca_vault = &mut ctx.accounts.dca_vault;
+    let vault_key = ctx.accounts.dca_vault.key();
+    let dca_vault = *ctx.accounts.dca_vault.load()?;
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
 
@@ -79,7 +92,7 @@ pub fn handler_withdraw_dca(ctx: Context<WithdrawDca>) -> Result<()> {
             Transfer {
                 from: ctx.accounts.vault_input_token.to_account_info(),
                 to: ctx.accounts.user_input_token.to_account_info(),
-                authority: dca_vault.to_account_info(),
+                authority: ctx.accounts.dca_vault.to_account_info(),
             },
             signer_seeds,
         );
@@ -93,35 +106,59 @@ pub fn handler_withdraw_dca(ctx: Context<WithdrawDca>) -> Result<()> {
             Transfer {
                 from: ctx.accounts.vault_output_token.to_account_info(),
                 to: ctx.accounts.user_output_token.to_account_info(),
-                authority: dca_vault.to_account_info(),
+                authority: ctx.accounts.dca_vault.to_account_info(),
             },
             signer_seeds,
         );
         transfer(transfer_output_ctx, accumulated_output)?;
     }
 
-    // Deactivate DCA
-    dca_vault.is_active = false;
+    // Deactivate DCA, dropping it from its mint's active count
+    let counted = {
+        let mut vault = ctx.accounts.dca_vault.load_mut()?;
+        vault.set_active(false);
+        vault.take_stats_count()
+    };
+    if counted {
+        ctx.accounts
+            .protocol_stats
+            .as_mut()
+            .ok_or(KryptosError::StatsShardRequired)?
+            .record_dca_deactivated();
+    }
 
-    // Emit events
+    // Emit events; input and output are different mints, so they are reported apart
+    let privacy = dca_vault.privacy_level();
     emit!(DcaCancelled {
-        vault: dca_vault.key(),
+        vault: vault_key,
         authority: dca_vault.authority,
-        remaining_amount: remaining_input,
+        remaining_amount: privacy.event_amount(remaining_input),
         cancelled_at: current_time,
     });
 
     emit!(FundsWithdrawn {
-        vault: dca_vault.key(),
+        vault: vault_key,
         authority: dca_vault.authority,
-        amount: remaining_input + accumulated_output,
+        amount: privacy.event_amount(remaining_input),
         vault_type: "DCA".to_string(),
         withdrawn_at: current_time,
     });
 
-    msg!("DCA withdrawn successfully");
-    msg!("Input returned: {}", remaining_input);
-    msg!("Output claimed: {}", accumulated_output);
+    if accumulated_output > 0 {
+        emit!(DcaOutputClaimed {
+            vault: vault_key,
+            authority: dca_vault.authority,
+            output_mint,
+            amount: privacy.event_amount(accumulated_output),
+            claimed_at: current_time,
+        });
+    }
+
+    if privacy.logs_enabled() {
+        msg!("DCA withdrawn successfully");
+        msg!("Input returned: {}", remaining_input);
+        msg!("Output claimed: {}", accumulated_output);
+    }
 
     Ok(())
 }
@@ -140,7 +177,6 @@ pub struct WithdrawIntent<'info> {
     #[account(
         mut,
         constraint = intent_vault.authority == authority.key() @ KryptosError::Unauthorized,
-        constraint = intent_vault.status != IntentStatus::Executed @ KryptosError::IntentAlreadyExecuted,
     )]
     pub intent_vault: Account<'info, IntentVault>,
 
@@ -155,6 +191,18 @@ pub struct WithdrawIntent<'info> {
     #[account(mut)]
     pub user_input_token: Account<'info, TokenAccount>,
 
+    /// Stats shard of the intent's input mint (may be omitted unless the
+    /// intent is still counted as active)
+    #[account(
+        mut,
+        seeds = [
+            ProtocolStats::SEED_PREFIX,
+            vault_input_token.mint.as_ref(),
+        ],
+        bump = protocol_stats.bump,
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
     /// Token program
     pub token_program: Program<'info, Token>,
 }
@@ -196,27 +244,38 @@ pub fn handler_withdraw_intent(ctx: Context<WithdrawIntent>) -> Result<()> {
         transfer(transfer_ctx, remaining_amount)?;
     }
 
-    // Update status
-    intent_vault.status = IntentStatus::Cancelled;
-
-    // Emit events
-    emit!(IntentCancelled {
-        vault: intent_vault.key(),
-        authority: intent_vault.authority,
-        remaining_amount,
-        cancelled_at: current_time,
-    });
+    // Executed intents only refund leftover dust and keep their status
+    let privacy = intent_vault.privacy_level;
+    if !intent_vault.withdraw_refunds_dust() {
+        intent_vault.status = IntentStatus::Cancelled;
+        if intent_vault.take_stats_count() {
+            ctx.accounts
+                .protocol_stats
+                .as_mut()
+                .ok_or(KryptosError::StatsShardRequired)?
+                .record_intent_deactivated();
+        }
+
+        emit!(IntentCancelled {
+            vault: intent_vault.key(),
+            authority: intent_vault.authority,
+            remaining_amount: privacy.event_amount(remaining_amount),
+            cancelled_at: current_time,
+        });
+    }
 
     emit!(FundsWithdrawn {
         vault: intent_vault.key(),
         authority: intent_vault.authority,
-        amount: remaining_amount,
+        amount: privacy.event_amount(remaining_amount),
         vault_type: "Intent".to_string(),
         withdrawn_at: current_time,
     });
 
-    msg!("Intent withdrawn successfully");
-    msg!("Amount returned: {}", remaining_amount);
+    if privacy.logs_enabled() {
+        msg!("Intent withdrawn successfully");
+        msg!("Amount returned: {}", remaining_amount);
+    }
 
     Ok(())
 }
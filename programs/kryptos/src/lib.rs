@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 
+pub mod entropy;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod metadata;
+pub mod oracle;
 pub mod state;
 
 // Re-export everything from instructions (includes Anchor-generated modules)
@@ -32,6 +35,17 @@ pub mod kryptos {
         instructions::execute_dca::handler(ctx, params)
     }
 
+    pub fn execute_dca_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteDcaBatch<'info>>,
+        params: ExecuteDcaBatchParams,
+    ) -> Result<()> {
+        instructions::execute_dca_batch::handler(ctx, params)
+    }
+
+    pub fn match_dca(ctx: Context<MatchDca>, params: MatchDcaParams) -> Result<()> {
+        instructions::match_dca::handler(ctx, params)
+    }
+
     pub fn withdraw_dca(ctx: Context<WithdrawDca>) -> Result<()> {
         instructions::withdraw::handler_withdraw_dca(ctx)
     }
@@ -40,6 +54,70 @@ pub mod kryptos {
         instructions::close::handler_close_dca(ctx)
     }
 
+    // ============================================
+    // BASKET DCA INSTRUCTIONS
+    // ============================================
+
+    pub fn initialize_basket_dca(
+        ctx: Context<InitializeBasketDca>,
+        params: InitializeBasketDcaParams,
+    ) -> Result<()> {
+        instructions::basket_dca::handler_initialize_basket_dca(ctx, params)
+    }
+
+    pub fn open_basket_output(ctx: Context<OpenBasketOutput>, output_index: u8) -> Result<()> {
+        instructions::basket_dca::handler_open_basket_output(ctx, output_index)
+    }
+
+    pub fn execute_basket_dca(
+        ctx: Context<ExecuteBasketDca>,
+        params: ExecuteBasketDcaParams,
+    ) -> Result<()> {
+        instructions::basket_dca::handler_execute_basket_dca(ctx, params)
+    }
+
+    pub fn withdraw_basket_dca<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawBasketDca<'info>>,
+    ) -> Result<()> {
+        instructions::basket_dca::handler_withdraw_basket_dca(ctx)
+    }
+
+    pub fn close_basket_dca<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseBasketDca<'info>>,
+    ) -> Result<()> {
+        instructions::basket_dca::handler_close_basket_dca(ctx)
+    }
+
+    // ============================================
+    // DCA POOL INSTRUCTIONS
+    // ============================================
+
+    pub fn initialize_dca_pool(
+        ctx: Context<InitializeDcaPool>,
+        params: InitializeDcaPoolParams,
+    ) -> Result<()> {
+        instructions::dca_pool::handler_initialize_dca_pool(ctx, params)
+    }
+
+    pub fn deposit_dca_pool(ctx: Context<DepositDcaPool>, amount: u64) -> Result<()> {
+        instructions::dca_pool::handler_deposit_dca_pool(ctx, amount)
+    }
+
+    pub fn withdraw_dca_pool(ctx: Context<WithdrawDcaPool>, shares: u128) -> Result<()> {
+        instructions::dca_pool::handler_withdraw_dca_pool(ctx, shares)
+    }
+
+    pub fn claim_dca_pool(ctx: Context<ClaimDcaPool>) -> Result<()> {
+        instructions::dca_pool::handler_claim_dca_pool(ctx)
+    }
+
+    pub fn execute_dca_pool(
+        ctx: Context<ExecuteDcaPool>,
+        params: ExecuteDcaPoolParams,
+    ) -> Result<()> {
+        instructions::dca_pool::handler_execute_dca_pool(ctx, params)
+    }
+
     // ============================================
     // INTENT INSTRUCTIONS
     // ============================================
@@ -58,6 +136,10 @@ pub mod kryptos {
         instructions::execute_intent::handler(ctx, params)
     }
 
+    pub fn update_trailing_intent(ctx: Context<UpdateTrailingIntent>) -> Result<()> {
+        instructions::update_trailing_intent::handler(ctx)
+    }
+
     pub fn withdraw_intent(ctx: Context<WithdrawIntent>) -> Result<()> {
         instructions::withdraw::handler_withdraw_intent(ctx)
     }
@@ -65,4 +147,131 @@ pub mod kryptos {
     pub fn close_intent(ctx: Context<CloseIntent>) -> Result<()> {
         instructions::close::handler_close_intent(ctx)
     }
+
+    // ============================================
+    // REBALANCE INSTRUCTIONS
+    // ============================================
+
+    pub fn initialize_rebalance(
+        ctx: Context<InitializeRebalance>,
+        params: InitializeRebalanceParams,
+    ) -> Result<()> {
+        instructions::rebalance::handler_initialize_rebalance(ctx, params)
+    }
+
+    pub fn open_rebalance_asset(ctx: Context<OpenRebalanceAsset>, asset_index: u8) -> Result<()> {
+        instructions::rebalance::handler_open_rebalance_asset(ctx, asset_index)
+    }
+
+    pub fn deposit_rebalance(
+        ctx: Context<DepositRebalance>,
+        asset_index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::rebalance::handler_deposit_rebalance(ctx, asset_index, amount)
+    }
+
+    pub fn rebalance<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>,
+        params: RebalanceParams,
+    ) -> Result<()> {
+        instructions::rebalance::handler_rebalance(ctx, params)
+    }
+
+    pub fn withdraw_rebalance<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawRebalance<'info>>,
+    ) -> Result<()> {
+        instructions::rebalance::handler_withdraw_rebalance(ctx)
+    }
+
+    pub fn close_rebalance<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseRebalance<'info>>,
+    ) -> Result<()> {
+        instructions::rebalance::handler_close_rebalance(ctx)
+    }
+
+    // ============================================
+    // STREAM INSTRUCTIONS
+    // ============================================
+
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        params: CreateStreamParams,
+    ) -> Result<()> {
+        instructions::stream::handler_create_stream(ctx, params)
+    }
+
+    pub fn claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+        instructions::stream::handler_claim_stream(ctx)
+    }
+
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        instructions::stream::handler_cancel_stream(ctx)
+    }
+
+    // ============================================
+    // DROP INSTRUCTIONS
+    // ============================================
+
+    pub fn create_drop(ctx: Context<CreateDrop>, params: CreateDropParams) -> Result<()> {
+        instructions::drop::handler_create_drop(ctx, params)
+    }
+
+    pub fn claim_drop(ctx: Context<ClaimDrop>) -> Result<()> {
+        instructions::drop::handler_claim_drop(ctx)
+    }
+
+    pub fn refund_drop(ctx: Context<RefundDrop>) -> Result<()> {
+        instructions::drop::handler_refund_drop(ctx)
+    }
+
+    // ============================================
+    // BURN INSTRUCTIONS
+    // ============================================
+
+    pub fn burn_with_receipt(ctx: Context<BurnWithReceipt>, amount: u64) -> Result<()> {
+        instructions::burn_with_receipt::handler(ctx, amount)
+    }
+
+    // ============================================
+    // MIGRATION INSTRUCTIONS
+    // ============================================
+
+    pub fn migrate_dca_vault(ctx: Context<MigrateDcaVault>) -> Result<()> {
+        instructions::migrate::handler_migrate_dca_vault(ctx)
+    }
+
+    pub fn migrate_intent_vault(ctx: Context<MigrateIntentVault>) -> Result<()> {
+        instructions::migrate::handler_migrate_intent_vault(ctx)
+    }
+
+    // ============================================
+    // PROTOCOL STATS INSTRUCTIONS
+    // ============================================
+
+    pub fn initialize_protocol_stats(ctx: Context<InitializeProtocolStats>) -> Result<()> {
+        instructions::protocol_stats::handler(ctx)
+    }
+
+    // ============================================
+    // VAULT METADATA INSTRUCTIONS
+    // ============================================
+
+    pub fn initialize_vault_metadata(
+        ctx: Context<InitializeVaultMetadata>,
+        len: u32,
+    ) -> Result<()> {
+        instructions::vault_metadata::handler_initialize_vault_metadata(ctx, len)
+    }
+
+    pub fn write_vault_metadata(
+        ctx: Context<WriteVaultMetadata>,
+        params: WriteVaultMetadataParams,
+    ) -> Result<()> {
+        instructions::vault_metadata::handler_write_vault_metadata(ctx, params)
+    }
+
+    pub fn close_vault_metadata(ctx: Context<CloseVaultMetadata>) -> Result<()> {
+        instructions::vault_metadata::handler_close_vault_metadata(ctx)
+    }
 }
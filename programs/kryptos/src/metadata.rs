@@ -0,0 +1,166 @@
+//! Client-side layout of encrypted vault metadata
+//!
+//! `VaultMetadata::data` holds a header followed by the ciphertext. The
+//! program treats it as opaque bytes; clients encrypt and decrypt with
+//! their own crypto libraries using the scheme below.
+//!
+//! ```text
+//! offset  size        field
+//! 0       1           version (METADATA_VERSION)
+//! 1       32          ephemeral X25519 public key
+//! 33      24          XChaCha20-Poly1305 nonce
+//! 57      1           recipient count (1-MAX_RECIPIENTS)
+//! 58      80 * count  recipients: X25519 public key (32) || wrapped data key (48)
+//! ...                 ciphertext of the metadata (plaintext + 16 byte tag)
+//! ```
+//!
+//! Encryption:
+//! 1. Draw a random 32 byte data key, a random nonce and an ephemeral X25519
+//!    key pair.
+//! 2. For every recipient (the owner, and the keeper if it should read the
+//!    strategy), derive `kek = HKDF-SHA256(ikm = X25519(ephemeral_secret,
+//!    recipient_public), salt = nonce, info = METADATA_KDF_INFO)` and wrap the
+//!    data key with `XChaCha20-Poly1305(kek, nonce)`.
+//! 3. Encrypt the metadata with `XChaCha20-Poly1305(data_key, nonce)`, using
+//!    the vault address as associated data so blobs cannot be moved between
+//!    vaults.
+//!
+//! A recipient finds its entry by public key, derives the same `kek` from its
+//! secret key and the ephemeral public key, unwraps the data key and decrypts.
+
+/// Current header version
+pub const METADATA_VERSION: u8 = 1;
+
+/// HKDF info string binding wrapped keys to this scheme
+pub const METADATA_KDF_INFO: &[u8] = b"kryptos-vault-metadata-v1";
+
+/// Maximum recipients a blob is encrypted to
+pub const MAX_RECIPIENTS: usize = 4;
+
+/// Size of a wrapped data key (32 byte key + 16 byte tag)
+pub const WRAPPED_KEY_LEN: usize = 48;
+
+/// Size of the fixed header part (before recipients)
+pub const HEADER_FIXED_LEN: usize = 1 + 32 + 24 + 1;
+
+/// Size of one recipient entry
+pub const RECIPIENT_LEN: usize = 32 + WRAPPED_KEY_LEN;
+
+/// Data key wrapped for one recipient
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MetadataRecipient {
+    /// Recipient's X25519 public key
+    pub public_key: [u8; 32],
+    /// Data key encrypted under the recipient's key-encryption key
+    pub wrapped_key: [u8; WRAPPED_KEY_LEN],
+}
+
+/// Key-exchange header at the start of every metadata blob
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MetadataHeader {
+    /// Sender's ephemeral X25519 public key
+    pub ephemeral_public_key: [u8; 32],
+    /// Nonce shared by the key wraps and the payload
+    pub nonce: [u8; 24],
+    /// Recipients able to decrypt the blob
+    pub recipients: Vec<MetadataRecipient>,
+}
+
+impl MetadataHeader {
+    /// Encoded size of this header
+    pub fn len(&self) -> usize {
+        HEADER_FIXED_LEN + self.recipients.len() * RECIPIENT_LEN
+    }
+
+    /// Check if the header has no recipients (and so cannot be decrypted)
+    pub fn is_empty(&self) -> bool {
+        self.recipients.is_empty()
+    }
+
+    /// Prepend the header to `ciphertext`, producing the blob to store
+    pub fn encode(&self, ciphertext: &[u8]) -> Option<Vec<u8>> {
+        if self.recipients.is_empty() || self.recipients.len() > MAX_RECIPIENTS {
+            return None;
+        }
+
+        let mut blob = Vec::with_capacity(self.len() + ciphertext.len());
+        blob.push(METADATA_VERSION);
+        blob.extend_from_slice(&self.ephemeral_public_key);
+        blob.extend_from_slice(&self.nonce);
+        blob.push(self.recipients.len() as u8);
+        for recipient in &self.recipients {
+            blob.extend_from_slice(&recipient.public_key);
+            blob.extend_from_slice(&recipient.wrapped_key);
+        }
+        blob.extend_from_slice(ciphertext);
+        Some(blob)
+    }
+
+    /// Split a stored blob into its header and ciphertext
+    pub fn decode(blob: &[u8]) -> Option<(Self, &[u8])> {
+        if blob.len() < HEADER_FIXED_LEN || blob[0] != METADATA_VERSION {
+            return None;
+        }
+
+        let ephemeral_public_key = blob[1..33].try_into().ok()?;
+        let nonce = blob[33..57].try_into().ok()?;
+        let count = blob[57] as usize;
+        if count == 0 || count > MAX_RECIPIENTS {
+            return None;
+        }
+
+        let recipients_end = HEADER_FIXED_LEN + count * RECIPIENT_LEN;
+        let recipients = blob
+            .get(HEADER_FIXED_LEN..recipients_end)?
+            .chunks_exact(RECIPIENT_LEN)
+            .map(|entry| {
+                Some(MetadataRecipient {
+                    public_key: entry[..32].try_into().ok()?,
+                    wrapped_key: entry[32..].try_into().ok()?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let header = Self {
+            ephemeral_public_key,
+            nonce,
+            recipients,
+        };
+        Some((header, &blob[recipients_end..]))
+    }
+
+    /// Find the key wrapped for `public_key`
+    pub fn recipient(&self, public_key: &[u8; 32]) -> Option<&MetadataRecipient> {
+        self.recipients.iter().find(|r| &r.public_key == public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_round_trips() {
+        let header = MetadataHeader {
+            ephemeral_public_key: [1u8; 32],
+            nonce: [2u8; 24],
+            recipients: vec![
+                MetadataRecipient { public_key: [3u8; 32], wrapped_key: [4u8; WRAPPED_KEY_LEN] },
+                MetadataRecipient { public_key: [5u8; 32], wrapped_key: [6u8; WRAPPED_KEY_LEN] },
+            ],
+        };
+        let blob = header.encode(b"ciphertext").unwrap();
+        assert_eq!(blob.len(), header.len() + 10);
+
+        let (decoded, ciphertext) = MetadataHeader::decode(&blob).unwrap();
+        assert_eq!(decoded, header);
+        assert_eq!(ciphertext, b"ciphertext");
+        assert!(decoded.recipient(&[5u8; 32]).is_some());
+
+        // Truncated recipients and unknown versions are rejected
+        assert!(MetadataHeader::decode(&blob[..HEADER_FIXED_LEN + 10]).is_none());
+        let mut unknown = blob.clone();
+        unknown[0] = 2;
+        assert!(MetadataHeader::decode(&unknown).is_none());
+    }
+}
@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::KryptosError;
+
+/// Pyth pull-oracle receiver program (owner of every `PriceUpdateV2` account)
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Anchor discriminator of Pyth's `PriceUpdateV2` account
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Maximum age of an oracle price accepted on-chain (seconds)
+pub const MAX_PRICE_AGE_SECONDS: i64 = 60;
+
+/// Maximum confidence interval accepted, relative to the price (basis points)
+pub const MAX_CONFIDENCE_BPS: u64 = 200;
+
+/// Decimals of every USD price handled by the program (e.g., 150000000 = $150)
+pub const PRICE_DECIMALS: u32 = 6;
+
+/// Pyth verification level of a posted update
+#[derive(AnchorDeserialize)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+/// Pyth price message as stored in `PriceUpdateV2`
+#[derive(AnchorDeserialize)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// Layout of Pyth's `PriceUpdateV2` account (after the discriminator)
+#[derive(AnchorDeserialize)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+/// Raw oracle price, worth `price * 10^exponent` USD
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: u64,
+    pub exponent: i32,
+}
+
+impl OraclePrice {
+    /// Price from a USD amount with `PRICE_DECIMALS` decimals
+    pub fn from_usd(price: u64) -> Self {
+        Self {
+            price,
+            exponent: -(PRICE_DECIMALS as i32),
+        }
+    }
+
+    /// Price in USD with `decimals` decimals
+    pub fn to_decimals(&self, decimals: u32) -> Option<u64> {
+        let scaled = scale(self.price as u128, self.exponent + decimals as i32)?;
+        u64::try_from(scaled).ok()
+    }
+}
+
+/// Read a fully verified Pyth price for `feed_id`, normalized to 6 decimals USD
+pub fn read_price(price_update: &AccountInfo, feed_id: &[u8; 32], current_time: i64) -> Result<u64> {
+    let oracle_price = read_oracle_price(price_update, feed_id, current_time)?;
+    let price = oracle_price
+        .to_decimals(PRICE_DECIMALS)
+        .ok_or(KryptosError::MathOverflow)?;
+    Ok(price)
+}
+
+/// Read a fully verified Pyth price for `feed_id` with its native exponent
+pub fn read_oracle_price(
+    price_update: &AccountInfo,
+    feed_id: &[u8; 32],
+    current_time: i64,
+) -> Result<OraclePrice> {
+    require_keys_eq!(
+        *price_update.owner,
+        PYTH_RECEIVER_PROGRAM_ID,
+        KryptosError::InvalidOracle
+    );
+
+    let data = price_update.try_borrow_data()?;
+    require!(
+        data.len() > 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+        KryptosError::InvalidOracle
+    );
+
+    let update = PriceUpdateV2::deserialize(&mut &data[8..])
+        .map_err(|_| KryptosError::InvalidOracle)?;
+    require!(
+        matches!(update.verification_level, VerificationLevel::Full),
+        KryptosError::InvalidOracle
+    );
+
+    let message = update.price_message;
+    require!(message.feed_id == *feed_id, KryptosError::OracleFeedMismatch);
+    require!(
+        current_time.saturating_sub(message.publish_time) <= MAX_PRICE_AGE_SECONDS,
+        KryptosError::StalePrice
+    );
+    require!(message.price > 0, KryptosError::InvalidOracle);
+
+    let price = message.price as u64;
+    require!(
+        (message.conf as u128) * 10_000 <= (price as u128) * MAX_CONFIDENCE_BPS as u128,
+        KryptosError::PriceTooUncertain
+    );
+
+    Ok(OraclePrice {
+        price,
+        exponent: message.exponent,
+    })
+}
+
+/// Read the price for `feed_id` from an optional instruction account
+pub fn read_feed_price(
+    price_update: Option<&UncheckedAccount>,
+    feed_id: &[u8; 32],
+    current_time: i64,
+) -> Result<u64> {
+    let price_update = price_update.ok_or(KryptosError::OracleRequired)?;
+    read_price(&price_update.to_account_info(), feed_id, current_time)
+}
+
+/// Read the raw price for `feed_id` from an optional instruction account
+pub fn read_feed_oracle_price(
+    price_update: Option<&UncheckedAccount>,
+    feed_id: &[u8; 32],
+    current_time: i64,
+) -> Result<OraclePrice> {
+    let price_update = price_update.ok_or(KryptosError::OracleRequired)?;
+    read_oracle_price(&price_update.to_account_info(), feed_id, current_time)
+}
+
+/// Price of `base` expressed in `quote` tokens with `decimals` decimals,
+/// computed from raw prices so low-priced tokens keep their precision
+pub fn cross_rate(base: OraclePrice, quote: OraclePrice, decimals: u8) -> Option<u64> {
+    if quote.price == 0 {
+        return None;
+    }
+
+    let shift = base.exponent - quote.exponent + decimals as i32;
+    let rate = if shift >= 0 {
+        scale(base.price as u128, shift)? / quote.price as u128
+    } else {
+        let divisor = scale(quote.price as u128, -shift)?;
+        base.price as u128 / divisor
+    };
+
+    u64::try_from(rate).ok()
+}
+
+/// Multiply `value` by `10^shift`, truncating when `shift` is negative
+fn scale(value: u128, shift: i32) -> Option<u128> {
+    if shift >= 0 {
+        10u128
+            .checked_pow(shift as u32)
+            .and_then(|factor| value.checked_mul(factor))
+    } else {
+        Some(
+            10u128
+                .checked_pow(shift.unsigned_abs())
+                .map(|factor| value / factor)
+                .unwrap_or(0),
+        )
+    }
+}
@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+
+use crate::state::randomized_next_execution;
+
+/// Maximum number of output tokens in a basket
+pub const MAX_BASKET_OUTPUTS: usize = 5;
+
+/// One output token of a basket
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct BasketOutput {
+    /// Token to buy
+    pub mint: Pubkey,
+    /// Token account receiving this output (default until opened)
+    pub vault: Pubkey,
+    /// Share of the deposit allocated to this output (basis points)
+    pub weight_bps: u16,
+    /// Input spent buying this output
+    pub total_spent: u64,
+    /// Output received so far
+    pub total_received: u64,
+    /// Output token account bump
+    pub vault_bump: u8,
+}
+
+impl BasketOutput {
+    pub const SPACE: usize = 32 +  // mint
+        32 +    // vault
+        2 +     // weight_bps
+        8 +     // total_spent
+        8 +     // total_received
+        1;      // vault_bump
+
+    /// Check if the output token account has been created
+    pub fn is_open(&self) -> bool {
+        self.vault != Pubkey::default()
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct BasketDcaVault {
+    // === Owner ===
+    /// User who created this basket
+    pub authority: Pubkey,
+    /// Unique nonce to allow multiple baskets per input token
+    pub nonce: u64,
+
+    // === Token Config ===
+    /// Token to spend (e.g., USDC)
+    pub input_mint: Pubkey,
+    /// Token account vault holding input tokens
+    pub input_vault: Pubkey,
+    /// Number of configured outputs
+    pub num_outputs: u8,
+    /// Output tokens with their weights and totals
+    pub outputs: [BasketOutput; MAX_BASKET_OUTPUTS],
+
+    // === DCA Config ===
+    /// Total amount to DCA (in smallest unit)
+    pub total_amount: u64,
+    /// Amount per execution (base, before variance)
+    pub amount_per_trade: u64,
+    /// Variance in basis points (e.g., 2000 = 20%)
+    pub variance_bps: u16,
+    /// Minimum number of executions per week
+    pub min_executions: u8,
+    /// Maximum number of executions per week
+    pub max_executions: u8,
+
+    // === Time Window ===
+    /// Execution window start hour (UTC, 0-23)
+    pub window_start_hour: u8,
+    /// Execution window end hour (UTC, 0-23)
+    pub window_end_hour: u8,
+
+    // === Tracking ===
+    /// Total amount spent so far (all outputs)
+    pub total_spent: u64,
+    /// Number of executions completed
+    pub execution_count: u32,
+    /// Timestamp of last execution
+    pub last_execution: i64,
+    /// Timestamp of next execution (randomized)
+    pub next_execution: i64,
+
+    // === Status ===
+    /// Whether the basket is still active
+    pub is_active: bool,
+    /// Creation timestamp
+    pub created_at: i64,
+
+    // === PDA Bumps ===
+    pub bump: u8,
+    pub input_vault_bump: u8,
+}
+
+impl BasketDcaVault {
+    /// Account space (8 discriminator + fields)
+    pub const SPACE: usize = 8 +  // discriminator
+        32 +    // authority
+        8 +     // nonce
+        32 +    // input_mint
+        32 +    // input_vault
+        1 +     // num_outputs
+        BasketOutput::SPACE * MAX_BASKET_OUTPUTS + // outputs
+        8 +     // total_amount
+        8 +     // amount_per_trade
+        2 +     // variance_bps
+        1 +     // min_executions
+        1 +     // max_executions
+        1 +     // window_start_hour
+        1 +     // window_end_hour
+        8 +     // total_spent
+        4 +     // execution_count
+        8 +     // last_execution
+        8 +     // next_execution
+        1 +     // is_active
+        8 +     // created_at
+        1 +     // bump
+        1 +     // input_vault_bump
+        64;     // padding for future use
+
+    /// PDA seeds prefix
+    pub const SEED_PREFIX: &'static [u8] = b"basket_dca_vault";
+
+    /// Output token account seeds prefix
+    pub const OUTPUT_SEED_PREFIX: &'static [u8] = b"basket_output";
+
+    /// Check if the basket is completed
+    pub fn is_completed(&self) -> bool {
+        self.total_spent >= self.total_amount
+    }
+
+    /// Check if execution is allowed now
+    pub fn can_execute(&self, current_time: i64) -> bool {
+        self.is_active
+            && !self.is_completed()
+            && current_time >= self.next_execution
+    }
+
+    /// Output the next execution must buy: the one furthest below its target
+    /// share once `swap_amount` more has been spent (ties go to the lowest index)
+    pub fn next_output_index(&self, swap_amount: u64) -> usize {
+        let spent_after = self.total_spent as u128 + swap_amount as u128;
+        let deficit = |output: &BasketOutput| {
+            (output.weight_bps as u128 * spent_after / 10_000) as i128 - output.total_spent as i128
+        };
+
+        let outputs = &self.outputs[..self.num_outputs as usize];
+        let mut best = 0;
+        for (index, output) in outputs.iter().enumerate().skip(1) {
+            if deficit(output) > deficit(&outputs[best]) {
+                best = index;
+            }
+        }
+        best
+    }
+
+    /// Randomize the next execution time after `current_time`
+    pub fn schedule_next_execution(&mut self, current_time: i64) {
+        self.next_execution =
+            randomized_next_execution(current_time, self.min_executions, self.max_executions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Basket splitting `weights` (basis points) with `spent` already bought per output
+    fn basket(weights: &[u16], spent: &[u64]) -> BasketDcaVault {
+        let mut vault = BasketDcaVault {
+            num_outputs: weights.len() as u8,
+            ..Default::default()
+        };
+        for (index, (weight_bps, total_spent)) in weights.iter().zip(spent).enumerate() {
+            vault.outputs[index].weight_bps = *weight_bps;
+            vault.outputs[index].total_spent = *total_spent;
+            vault.total_spent += total_spent;
+        }
+        vault
+    }
+
+    #[test]
+    fn next_output_follows_weights() {
+        // Nothing bought yet: the heaviest output goes first
+        assert_eq!(basket(&[2_000, 5_000, 3_000], &[0, 0, 0]).next_output_index(100), 1);
+
+        // 60/40 basket: after 100 on the first, the second is furthest behind
+        assert_eq!(basket(&[6_000, 4_000], &[100, 0]).next_output_index(100), 1);
+        assert_eq!(basket(&[6_000, 4_000], &[100, 100]).next_output_index(100), 0);
+    }
+
+    #[test]
+    fn next_output_breaks_ties_and_ignores_unused_slots() {
+        // Equal deficits go to the lowest index
+        assert_eq!(basket(&[5_000, 5_000], &[0, 0]).next_output_index(100), 0);
+        assert_eq!(basket(&[5_000, 5_000], &[50, 50]).next_output_index(100), 0);
+
+        // Slots past `num_outputs` are never picked, even with stale weights
+        let mut vault = basket(&[5_000, 5_000], &[100, 0]);
+        vault.outputs[2].weight_bps = 10_000;
+        assert_eq!(vault.next_output_index(100), 1);
+
+        // Overbought outputs (negative deficit) still compare correctly
+        assert_eq!(basket(&[1_000, 9_000], &[500, 0]).next_output_index(0), 1);
+    }
+
+    #[test]
+    fn repeated_picks_converge_to_weights() {
+        let mut vault = basket(&[2_500, 2_500, 5_000], &[0, 0, 0]);
+        for _ in 0..40 {
+            let index = vault.next_output_index(10);
+            vault.outputs[index].total_spent += 10;
+            vault.total_spent += 10;
+        }
+        let spent: Vec<u64> = vault.outputs[..3].iter().map(|output| output.total_spent).collect();
+        assert_eq!(spent, vec![100, 100, 200]);
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct BurnReceipt {
+    // === Owner ===
+    /// User who burned the tokens
+    pub authority: Pubkey,
+    /// Token burned
+    pub mint: Pubkey,
+
+    // === Last Burn ===
+    /// Amount burned by the latest burn
+    pub last_amount: u64,
+    /// Timestamp of the latest burn
+    pub last_burned_at: i64,
+
+    // === Totals ===
+    /// Cumulative amount burned by this user for this mint
+    pub total_burned: u64,
+    /// Number of burns recorded
+    pub burn_count: u32,
+    /// Timestamp of the first burn
+    pub first_burned_at: i64,
+
+    // === PDA Bumps ===
+    pub bump: u8,
+}
+
+impl BurnReceipt {
+    /// Account space (8 discriminator + fields)
+    pub const SPACE: usize = 8 +  // discriminator
+        32 +    // authority
+        32 +    // mint
+        8 +     // last_amount
+        8 +     // last_burned_at
+        8 +     // total_burned
+        4 +     // burn_count
+        8 +     // first_burned_at
+        1 +     // bump
+        32;     // padding for future use
+
+    /// PDA seeds prefix
+    pub const SEED_PREFIX: &'static [u8] = b"burn_receipt";
+}
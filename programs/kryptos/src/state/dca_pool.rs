@@ -0,0 +1,419 @@
+use anchor_lang::prelude::*;
+
+/// Fixed-point scale of `DcaPool::acc_output_per_share`
+pub const ACC_PRECISION: u128 = 1_000_000_000_000;
+
+#[account]
+#[derive(Default)]
+pub struct DcaPool {
+    // === Token Config ===
+    /// Token to spend (e.g., USDC)
+    pub input_mint: Pubkey,
+    /// Token to buy (e.g., SOL)
+    pub output_mint: Pubkey,
+    /// Token account vault holding pooled input
+    pub input_vault: Pubkey,
+    /// Token account vault holding bought output until claimed
+    pub output_vault: Pubkey,
+
+    // === Pool Config ===
+    /// Seconds between executions
+    pub cadence_seconds: u32,
+    /// Share of the pooled input spent per execution (basis points)
+    pub spend_bps: u16,
+
+    // === Accounting ===
+    /// Shares outstanding across all positions
+    pub total_shares: u128,
+    /// Input held for depositors (excludes donations to the vault)
+    pub total_input: u64,
+    /// Output earned per share, scaled by `ACC_PRECISION`
+    pub acc_output_per_share: u128,
+    /// Share epoch; a sweep that drains the pool ends it and restarts the
+    /// share accounting
+    pub epoch: u32,
+
+    // === Tracking ===
+    /// Total input spent so far
+    pub total_spent: u64,
+    /// Total output received so far
+    pub total_received: u64,
+    /// Number of executions completed
+    pub execution_count: u32,
+    /// Timestamp of last execution
+    pub last_execution: i64,
+    /// Timestamp of next execution
+    pub next_execution: i64,
+    /// Creation timestamp
+    pub created_at: i64,
+
+    // === PDA Bumps ===
+    pub bump: u8,
+    pub input_vault_bump: u8,
+    pub output_vault_bump: u8,
+}
+
+impl DcaPool {
+    /// Account space (8 discriminator + fields)
+    pub const SPACE: usize = 8 +  // discriminator
+        32 +    // input_mint
+        32 +    // output_mint
+        32 +    // input_vault
+        32 +    // output_vault
+        4 +     // cadence_seconds
+        2 +     // spend_bps
+        16 +    // total_shares
+        8 +     // total_input
+        16 +    // acc_output_per_share
+        4 +     // epoch
+        8 +     // total_spent
+        8 +     // total_received
+        4 +     // execution_count
+        8 +     // last_execution
+        8 +     // next_execution
+        8 +     // created_at
+        1 +     // bump
+        1 +     // input_vault_bump
+        1 +     // output_vault_bump
+        60;     // padding for future use
+
+    /// PDA seeds prefix
+    pub const SEED_PREFIX: &'static [u8] = b"dca_pool";
+
+    /// Input the next execution must spend: `spend_bps` of the pool, or all
+    /// of it once that share rounds to 0 (the final sweep)
+    pub fn execution_amount(&self) -> u64 {
+        let amount = (self.total_input as u128 * self.spend_bps as u128 / 10_000) as u64;
+        if amount == 0 {
+            self.total_input
+        } else {
+            amount
+        }
+    }
+
+    /// Check if the final sweep spent all input while shares are still out;
+    /// those shares are worth no input, so the epoch has to end before new
+    /// deposits come in
+    pub fn is_drained(&self) -> bool {
+        self.total_shares > 0 && self.total_input == 0
+    }
+
+    /// End the current epoch: outstanding shares are retired against the
+    /// epoch's final `acc_output_per_share` (kept in a `DcaPoolEpoch`), and
+    /// the pool takes deposits at 1:1 again
+    pub fn start_next_epoch(&mut self) -> Option<()> {
+        self.epoch = self.epoch.checked_add(1)?;
+        self.total_shares = 0;
+        self.acc_output_per_share = 0;
+        Some(())
+    }
+
+    /// Book an execution that spent `spent` input for `received` output,
+    /// distributing the output across all shares
+    pub fn record_execution(&mut self, spent: u64, received: u64) -> Option<()> {
+        let output_per_share = (received as u128)
+            .checked_mul(ACC_PRECISION)?
+            .checked_div(self.total_shares)?;
+        self.acc_output_per_share = self.acc_output_per_share.checked_add(output_per_share)?;
+        self.total_input = self.total_input.checked_sub(spent)?;
+        self.total_spent = self.total_spent.checked_add(spent)?;
+        self.total_received = self.total_received.checked_add(received)?;
+        self.execution_count = self.execution_count.checked_add(1)?;
+        Some(())
+    }
+
+    /// Shares minted for a deposit of `amount`
+    pub fn shares_for_deposit(&self, amount: u64) -> Option<u128> {
+        if self.total_shares == 0 || self.total_input == 0 {
+            return Some(amount as u128);
+        }
+        (amount as u128)
+            .checked_mul(self.total_shares)?
+            .checked_div(self.total_input as u128)
+    }
+
+    /// Input returned for redeeming `shares`
+    pub fn input_for_shares(&self, shares: u128) -> Option<u64> {
+        if self.total_shares == 0 {
+            return Some(0);
+        }
+        let amount = shares
+            .checked_mul(self.total_input as u128)?
+            .checked_div(self.total_shares)?;
+        u64::try_from(amount).ok()
+    }
+
+    /// Output accrued by `shares` since the pool started
+    pub fn accrued_output(&self, shares: u128) -> Option<u128> {
+        shares
+            .checked_mul(self.acc_output_per_share)?
+            .checked_div(ACC_PRECISION)
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct DcaPoolPosition {
+    /// Pool the position belongs to
+    pub pool: Pubkey,
+    /// Depositor
+    pub owner: Pubkey,
+    /// Pool shares held
+    pub shares: u128,
+    /// Output already accounted for at the current share count
+    pub output_debt: u128,
+    /// Output settled but not yet claimed
+    pub pending_output: u64,
+    /// Total output claimed
+    pub total_claimed: u64,
+    /// Pool epoch the shares were minted in
+    pub epoch: u32,
+    pub bump: u8,
+}
+
+impl DcaPoolPosition {
+    /// Account space (8 discriminator + fields)
+    pub const SPACE: usize = 8 +  // discriminator
+        32 +    // pool
+        32 +    // owner
+        16 +    // shares
+        16 +    // output_debt
+        8 +     // pending_output
+        8 +     // total_claimed
+        4 +     // epoch
+        1 +     // bump
+        28;     // padding for future use
+
+    /// PDA seeds prefix
+    pub const SEED_PREFIX: &'static [u8] = b"dca_pool_position";
+
+    /// Move output accrued since the last update into `pending_output`
+    pub fn settle(&mut self, pool: &DcaPool) -> Option<()> {
+        let accrued = pool.accrued_output(self.shares)?;
+        let earned = u64::try_from(accrued.checked_sub(self.output_debt)?).ok()?;
+        self.pending_output = self.pending_output.checked_add(earned)?;
+        Some(())
+    }
+
+    /// Reset the debt after the share count changed
+    pub fn sync_debt(&mut self, pool: &DcaPool) -> Option<()> {
+        self.output_debt = pool.accrued_output(self.shares)?;
+        Some(())
+    }
+
+    /// Check if the shares belong to an epoch the pool has ended
+    pub fn is_stale(&self, pool: &DcaPool) -> bool {
+        self.epoch != pool.epoch
+    }
+
+    /// Settle stale shares against the final `acc_output_per_share` of their
+    /// epoch, then drop them and join the pool's current epoch
+    pub fn retire(&mut self, ended_acc_output_per_share: u128, epoch: u32) -> Option<()> {
+        let accrued = self
+            .shares
+            .checked_mul(ended_acc_output_per_share)?
+            .checked_div(ACC_PRECISION)?;
+        let earned = u64::try_from(accrued.checked_sub(self.output_debt)?).ok()?;
+        self.pending_output = self.pending_output.checked_add(earned)?;
+        self.shares = 0;
+        self.output_debt = 0;
+        self.epoch = epoch;
+        Some(())
+    }
+}
+
+/// Final accounting of an ended pool epoch, kept so positions from that
+/// epoch can still settle their output
+#[account]
+#[derive(Default)]
+pub struct DcaPoolEpoch {
+    /// Pool the epoch belongs to
+    pub pool: Pubkey,
+    /// Ended epoch
+    pub epoch: u32,
+    /// `acc_output_per_share` when the epoch ended
+    pub acc_output_per_share: u128,
+    pub bump: u8,
+}
+
+impl DcaPoolEpoch {
+    /// Account space (8 discriminator + fields)
+    pub const SPACE: usize = 8 +  // discriminator
+        32 +    // pool
+        4 +     // epoch
+        16 +    // acc_output_per_share
+        1;      // bump
+
+    /// PDA seeds prefix
+    pub const SEED_PREFIX: &'static [u8] = b"dca_pool_epoch";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pool spending 10% per execution
+    fn pool() -> DcaPool {
+        DcaPool {
+            spend_bps: 1_000,
+            ..Default::default()
+        }
+    }
+
+    /// Deposit as `handler_deposit_dca_pool` books it
+    fn deposit(pool: &mut DcaPool, position: &mut DcaPoolPosition, amount: u64) {
+        assert!(!position.is_stale(pool));
+        position.settle(pool).unwrap();
+        let shares = pool.shares_for_deposit(amount).unwrap();
+        position.shares += shares;
+        pool.total_shares += shares;
+        pool.total_input += amount;
+        position.sync_debt(pool).unwrap();
+    }
+
+    /// Withdraw as `handler_withdraw_dca_pool` books it, returning the input
+    fn withdraw(pool: &mut DcaPool, position: &mut DcaPoolPosition, shares: u128) -> u64 {
+        position.settle(pool).unwrap();
+        let amount = pool.input_for_shares(shares).unwrap();
+        position.shares -= shares;
+        pool.total_shares -= shares;
+        pool.total_input -= amount;
+        position.sync_debt(pool).unwrap();
+        amount
+    }
+
+    /// Claim as `handler_claim_dca_pool` books it, returning the output
+    fn claim(pool: &DcaPool, position: &mut DcaPoolPosition) -> u64 {
+        position.settle(pool).unwrap();
+        position.sync_debt(pool).unwrap();
+        std::mem::take(&mut position.pending_output)
+    }
+
+    /// Roll a stale position as `roll_position` does; `ended` holds the final
+    /// accumulator of each ended epoch
+    fn roll(pool: &DcaPool, ended: &[u128], position: &mut DcaPoolPosition) {
+        if position.is_stale(pool) {
+            let acc = if position.shares == 0 { 0 } else { ended[position.epoch as usize] };
+            position.retire(acc, pool.epoch).unwrap();
+        }
+    }
+
+    fn execute(pool: &mut DcaPool, received: u64) -> u64 {
+        let spent = pool.execution_amount();
+        pool.record_execution(spent, received).unwrap();
+        spent
+    }
+
+    #[test]
+    fn execution_amount_sweeps_the_last_dust() {
+        let mut pool = DcaPool { total_input: 1_000, ..pool() };
+        assert_eq!(pool.execution_amount(), 100);
+
+        // 10% of 9 rounds to 0, so the final execution takes all 9
+        pool.total_input = 9;
+        assert_eq!(pool.execution_amount(), 9);
+
+        pool.total_input = 0;
+        assert_eq!(pool.execution_amount(), 0);
+    }
+
+    #[test]
+    fn shares_track_input_per_share() {
+        let mut pool = pool();
+        assert_eq!(pool.shares_for_deposit(500), Some(500));
+        assert_eq!(pool.input_for_shares(10), Some(0));
+
+        // After spending half the pool, shares cost half as much input
+        pool.total_shares = 1_000;
+        pool.total_input = 500;
+        assert_eq!(pool.shares_for_deposit(100), Some(200));
+        assert_eq!(pool.input_for_shares(200), Some(100));
+        assert_eq!(pool.input_for_shares(1_000), Some(500));
+        assert_eq!(pool.input_for_shares(u128::MAX), None);
+    }
+
+    #[test]
+    fn settle_moves_accrued_output_once() {
+        let pool = DcaPool {
+            total_shares: 100,
+            acc_output_per_share: 3 * ACC_PRECISION,
+            ..pool()
+        };
+        let mut position = DcaPoolPosition { shares: 10, output_debt: 10, ..Default::default() };
+        position.settle(&pool).unwrap();
+        assert_eq!(position.pending_output, 20);
+
+        // Syncing the debt stops the same output being settled twice
+        position.sync_debt(&pool).unwrap();
+        position.settle(&pool).unwrap();
+        assert_eq!(position.pending_output, 20);
+    }
+
+    #[test]
+    fn depositors_share_output_by_time_in_pool() {
+        let mut pool = pool();
+        let (mut alice, mut bob) = (DcaPoolPosition::default(), DcaPoolPosition::default());
+
+        deposit(&mut pool, &mut alice, 1_000);
+        assert_eq!(execute(&mut pool, 50), 100);
+
+        // Bob joins at 900 input for 1000 shares: his 900 buys 1000 shares
+        deposit(&mut pool, &mut bob, 900);
+        assert_eq!(bob.shares, 1_000);
+        assert_eq!(execute(&mut pool, 90), 180);
+
+        // Alice earned all of the first fill and half of the second
+        assert_eq!(claim(&pool, &mut alice), 95);
+        assert_eq!(claim(&pool, &mut bob), 45);
+        assert_eq!(claim(&pool, &mut alice), 0);
+
+        // Withdrawals return the unspent input pro rata and stop accrual
+        assert_eq!(withdraw(&mut pool, &mut alice, 1_000), 810);
+        execute(&mut pool, 10);
+        assert_eq!(claim(&pool, &mut alice), 0);
+        assert_eq!(claim(&pool, &mut bob), 10);
+        assert_eq!(pool.total_input, 729);
+    }
+
+    #[test]
+    fn draining_sweep_starts_a_new_epoch() {
+        let mut pool = pool();
+        let (mut alice, mut bob) = (DcaPoolPosition::default(), DcaPoolPosition::default());
+        deposit(&mut pool, &mut alice, 100);
+        deposit(&mut pool, &mut bob, 100);
+
+        // Geometric decay ends in a sweep instead of a stuck pool
+        execute(&mut pool, 2);
+        assert_eq!(claim(&pool, &mut alice), 1);
+        let mut executions = 1;
+        while pool.total_input > 0 {
+            execute(&mut pool, 2);
+            executions += 1;
+        }
+        assert!(executions < 50);
+        assert!(pool.is_drained());
+
+        // Execution records the final accumulator and restarts the shares
+        let ended = [pool.acc_output_per_share];
+        pool.start_next_epoch().unwrap();
+        assert_eq!(pool.epoch, 1);
+        assert!(!pool.is_drained());
+
+        // A new depositor gets shares at 1:1 and none of the old output
+        let mut carol = DcaPoolPosition::default();
+        roll(&pool, &ended, &mut carol);
+        deposit(&mut pool, &mut carol, 50);
+        assert_eq!(carol.shares, 50);
+        assert_eq!(pool.input_for_shares(50), Some(50));
+        execute(&mut pool, 10);
+        assert_eq!(claim(&pool, &mut carol), 10);
+
+        // Stale positions settle against their own epoch and hold no shares
+        roll(&pool, &ended, &mut alice);
+        roll(&pool, &ended, &mut bob);
+        assert_eq!((alice.shares, alice.epoch), (0, 1));
+        assert_eq!(claim(&pool, &mut alice), executions - 1);
+        assert_eq!(claim(&pool, &mut bob), executions);
+        assert_eq!(claim(&pool, &mut carol), 0);
+    }
+}
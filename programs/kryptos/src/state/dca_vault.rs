@@ -1,6 +1,48 @@
 use anchor_lang::prelude::*;
 
-#[account]
+use crate::errors::KryptosError;
+use crate::state::PrivacyLevel;
+
+/// How much each DCA execution buys
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum DcaStrategy {
+    /// Fixed `amount_per_trade` (with variance)
+    #[default]
+    FixedAmount,
+    /// Enough to grow the output position's USD value by `target_increment` per period
+    ValueAveraging,
+}
+
+/// Direction of a DCA vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum DcaSide {
+    /// Accumulate the output token; proceeds stay in the output vault
+    #[default]
+    Buy,
+    /// Gradually exit the input token; proceeds stream to the owner's wallet
+    Sell,
+}
+
+/// Why a scheduled DCA execution was skipped
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DcaSkipReason {
+    /// Price was above `max_price`
+    PriceAboveMax,
+    /// Price was below `min_price`
+    PriceBelowMin,
+    /// Value averaging position was already at or above its target value
+    AboveTargetValue,
+}
+
+/// DCA vault, stored zero-copy with a fixed `#[repr(C)]` layout
+///
+/// Fields are ordered by alignment so the struct has no implicit padding and
+/// every offset is stable, letting RPC `memcmp` filters select vaults by
+/// `is_active` or `next_execution`. Enums and flags are stored as `u8`; use
+/// the typed accessors (`is_active()`, `strategy()`, `side()`,
+/// `privacy_level()`) to read them.
+#[account(zero_copy)]
+#[repr(C)]
 #[derive(Default)]
 pub struct DcaVault {
     // === Owner ===
@@ -22,8 +64,48 @@ pub struct DcaVault {
     pub total_amount: u64,
     /// Amount per execution (base, before variance)
     pub amount_per_trade: u64,
+    
+    // === Tracking ===
+    /// Total amount spent so far
+    pub total_spent: u64,
+    /// Total amount received so far
+    pub total_received: u64,
+    /// Timestamp of last execution
+    pub last_execution: i64,
+    /// Timestamp of next execution (randomized)
+    pub next_execution: i64,
+    /// Creation timestamp
+    pub created_at: i64,
+
+    // === Price Guard ===
+    /// Skip executions while the guarded price is below this (6 decimals USD, 0 = off);
+    /// the price floor of Sell vaults
+    pub min_price: u64,
+    /// Skip executions while the guarded price is above this (6 decimals USD, 0 = off)
+    pub max_price: u64,
+
+    // === Strategy ===
+    /// For ValueAveraging: target USD value added per period (6 decimals)
+    pub target_increment: u64,
+    /// For ValueAveraging: minimum input per execution
+    pub min_trade_amount: u64,
+    /// For ValueAveraging: maximum input per execution
+    pub max_trade_amount: u64,
+
+    // === Oracle Feeds ===
+    /// Pyth feed id of the output token (zero = not priced)
+    pub output_feed_id: [u8; 32],
+    /// Pyth feed id of the input token (zero = not priced)
+    pub input_feed_id: [u8; 32],
+
+    // === Counters And Limits ===
+    /// Number of executions completed
+    pub execution_count: u32,
     /// Variance in basis points (e.g., 2000 = 20%)
     pub variance_bps: u16,
+    /// Maximum value given up when matched against an opposing vault, at
+    /// oracle prices (basis points, 0 = never matched)
+    pub max_slippage_bps: u16,
     /// Minimum number of executions per week
     pub min_executions: u8,
     /// Maximum number of executions per week
@@ -35,59 +117,220 @@ pub struct DcaVault {
     /// Execution window end hour (UTC, 0-23)
     pub window_end_hour: u8,
     
-    // === Tracking ===
-    /// Total amount spent so far
+    // === Status ===
+    /// Whether DCA is still active (0/1, see `is_active()`)
+    pub is_active: u8,
+    
+    // === PDA Bumps ===
+    pub bump: u8,
+    pub input_vault_bump: u8,
+    pub output_vault_bump: u8,
+
+    // === Enums (stored as u8) ===
+    /// `DcaStrategy`: how much each execution buys
+    pub strategy: u8,
+    /// `DcaSide`: Buy accumulates output; Sell exits input with streamed proceeds
+    pub side: u8,
+    /// `PrivacyLevel`: what logs and events reveal about executions
+    pub privacy_level: u8,
+
+    // === Decimals ===
+    /// Input mint decimals
+    pub input_decimals: u8,
+    /// Output mint decimals
+    pub output_decimals: u8,
+
+    // === Versioning ===
+    /// Layout version (see `migrate`)
+    pub version: u8,
+
+    // === Stats ===
+    /// Whether the vault is counted as active in its input mint's stats shard
+    /// (0/1, see `take_stats_count()`); cleared once it completes, is withdrawn
+    /// or closed, and never set for vaults opened before shards existed
+    pub stats_counted: u8,
+    /// Explicit padding to the 8 byte alignment of `reserved`
+    pub padding: [u8; 1],
+    /// Reserved for future fields (always zero)
+    pub reserved: [u64; 8],
+}
+
+/// Borsh layout of `DcaVault` before it became zero-copy (layout versions 0 and 1)
+///
+/// Version 0 accounts were written before `version`/`reserved` existed and are
+/// shorter; they decode once zero-extended, with the missing fields as defaults.
+#[derive(AnchorDeserialize, Default)]
+pub struct LegacyDcaVault {
+    pub authority: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub input_vault: Pubkey,
+    pub output_vault: Pubkey,
+    pub total_amount: u64,
+    pub amount_per_trade: u64,
+    pub variance_bps: u16,
+    pub min_executions: u8,
+    pub max_executions: u8,
+    pub window_start_hour: u8,
+    pub window_end_hour: u8,
     pub total_spent: u64,
-    /// Total amount received so far
     pub total_received: u64,
-    /// Number of executions completed
     pub execution_count: u32,
-    /// Timestamp of last execution
     pub last_execution: i64,
-    /// Timestamp of next execution (randomized)
     pub next_execution: i64,
-    
-    // === Status ===
-    /// Whether DCA is still active
     pub is_active: bool,
-    /// Creation timestamp
     pub created_at: i64,
-    
-    // === PDA Bumps ===
     pub bump: u8,
     pub input_vault_bump: u8,
     pub output_vault_bump: u8,
+    pub output_feed_id: [u8; 32],
+    pub min_price: u64,
+    pub max_price: u64,
+    pub strategy: DcaStrategy,
+    pub input_feed_id: [u8; 32],
+    pub target_increment: u64,
+    pub min_trade_amount: u64,
+    pub max_trade_amount: u64,
+    pub input_decimals: u8,
+    pub output_decimals: u8,
+    pub side: DcaSide,
+    pub max_slippage_bps: u16,
+    pub privacy_level: PrivacyLevel,
+    pub version: u8,
+    pub reserved: [u64; 8],
+}
+
+impl LegacyDcaVault {
+    /// Account space of the last Borsh layout (version 1)
+    pub const SPACE: usize = 414;
+}
+
+impl From<LegacyDcaVault> for DcaVault {
+    fn from(legacy: LegacyDcaVault) -> Self {
+        Self {
+            authority: legacy.authority,
+            input_mint: legacy.input_mint,
+            output_mint: legacy.output_mint,
+            input_vault: legacy.input_vault,
+            output_vault: legacy.output_vault,
+            total_amount: legacy.total_amount,
+            amount_per_trade: legacy.amount_per_trade,
+            total_spent: legacy.total_spent,
+            total_received: legacy.total_received,
+            last_execution: legacy.last_execution,
+            next_execution: legacy.next_execution,
+            created_at: legacy.created_at,
+            min_price: legacy.min_price,
+            max_price: legacy.max_price,
+            target_increment: legacy.target_increment,
+            min_trade_amount: legacy.min_trade_amount,
+            max_trade_amount: legacy.max_trade_amount,
+            output_feed_id: legacy.output_feed_id,
+            input_feed_id: legacy.input_feed_id,
+            execution_count: legacy.execution_count,
+            variance_bps: legacy.variance_bps,
+            max_slippage_bps: legacy.max_slippage_bps,
+            min_executions: legacy.min_executions,
+            max_executions: legacy.max_executions,
+            window_start_hour: legacy.window_start_hour,
+            window_end_hour: legacy.window_end_hour,
+            is_active: legacy.is_active as u8,
+            bump: legacy.bump,
+            input_vault_bump: legacy.input_vault_bump,
+            output_vault_bump: legacy.output_vault_bump,
+            strategy: legacy.strategy as u8,
+            side: legacy.side as u8,
+            privacy_level: legacy.privacy_level as u8,
+            input_decimals: legacy.input_decimals,
+            output_decimals: legacy.output_decimals,
+            version: DcaVault::LAYOUT_VERSION,
+            stats_counted: 0,
+            padding: [0; 1],
+            reserved: [0; 8],
+        }
+    }
 }
 
 impl DcaVault {
     /// Account space (8 discriminator + fields)
-    pub const SPACE: usize = 8 +  // discriminator
-        32 +    // authority
-        32 +    // input_mint
-        32 +    // output_mint
-        32 +    // input_vault
-        32 +    // output_vault
-        8 +     // total_amount
-        8 +     // amount_per_trade
-        2 +     // variance_bps
-        1 +     // min_executions
-        1 +     // max_executions
-        1 +     // window_start_hour
-        1 +     // window_end_hour
-        8 +     // total_spent
-        8 +     // total_received
-        4 +     // execution_count
-        8 +     // last_execution
-        8 +     // next_execution
-        1 +     // is_active
-        8 +     // created_at
-        1 +     // bump
-        1 +     // input_vault_bump
-        1 +     // output_vault_bump
-        64;     // padding for future use
+    pub const SPACE: usize = 8 + core::mem::size_of::<DcaVault>();
 
     /// PDA seeds prefix
     pub const SEED_PREFIX: &'static [u8] = b"dca_vault";
+
+    /// Byte offset of `is_active` in the account data, for RPC `memcmp` filters
+    pub const IS_ACTIVE_OFFSET: usize = 8 + core::mem::offset_of!(DcaVault, is_active);
+
+    /// Byte offset of `next_execution` in the account data, for RPC `memcmp` filters
+    pub const NEXT_EXECUTION_OFFSET: usize = 8 + core::mem::offset_of!(DcaVault, next_execution);
+
+    /// Layout version written by this program (2 = zero-copy)
+    pub const LAYOUT_VERSION: u8 = 2;
+
+    /// Decode a vault written by a Borsh layout (versions 0 and 1)
+    ///
+    /// Legacy accounts are at most `LegacyDcaVault::SPACE` bytes, which is
+    /// how they are told apart from zero-copy accounts sharing the
+    /// discriminator. The result is stamped with `LAYOUT_VERSION`.
+    pub fn from_legacy(data: &[u8]) -> Result<Self> {
+        require!(
+            data.starts_with(Self::DISCRIMINATOR),
+            KryptosError::InvalidMigrationAccount
+        );
+        require!(
+            data.len() <= LegacyDcaVault::SPACE,
+            KryptosError::VaultAlreadyMigrated
+        );
+
+        // Every Borsh layout only appended fields, so the zero-filled tail
+        // decodes as their defaults
+        let mut body = data[8..].to_vec();
+        body.resize(LegacyDcaVault::SPACE - 8, 0);
+        let legacy = LegacyDcaVault::deserialize(&mut &body[..])?;
+        Ok(Self::from(legacy))
+    }
+
+    /// Check if the vault is still active
+    pub fn is_active(&self) -> bool {
+        self.is_active != 0
+    }
+
+    /// Activate or deactivate the vault
+    pub fn set_active(&mut self, active: bool) {
+        self.is_active = active as u8;
+    }
+
+    /// Stop counting the vault as active, returning whether its stats shard
+    /// must be decremented (true at most once per vault)
+    pub fn take_stats_count(&mut self) -> bool {
+        let counted = self.stats_counted != 0;
+        self.stats_counted = 0;
+        counted
+    }
+
+    /// How much each execution buys
+    pub fn strategy(&self) -> DcaStrategy {
+        match self.strategy {
+            1 => DcaStrategy::ValueAveraging,
+            _ => DcaStrategy::FixedAmount,
+        }
+    }
+
+    /// Direction of the vault
+    pub fn side(&self) -> DcaSide {
+        match self.side {
+            1 => DcaSide::Sell,
+            _ => DcaSide::Buy,
+        }
+    }
+
+    /// What logs and events reveal about executions
+    pub fn privacy_level(&self) -> PrivacyLevel {
+        match self.privacy_level {
+            0 => PrivacyLevel::Public,
+            _ => PrivacyLevel::Private,
+        }
+    }
     
     /// Check if DCA is completed
     pub fn is_completed(&self) -> bool {
@@ -96,8 +339,443 @@ impl DcaVault {
     
     /// Check if execution is allowed now
     pub fn can_execute(&self, current_time: i64) -> bool {
-        self.is_active 
+        self.is_active()
             && !self.is_completed()
             && current_time >= self.next_execution
     }
+
+    /// Check if executions are conditional on the output price
+    pub fn has_price_guard(&self) -> bool {
+        self.min_price > 0 || self.max_price > 0
+    }
+
+    /// Check the price guard, returning why the execution must be skipped
+    pub fn check_price_guard(&self, current_price: u64) -> Option<DcaSkipReason> {
+        if self.max_price > 0 && current_price > self.max_price {
+            Some(DcaSkipReason::PriceAboveMax)
+        } else if self.min_price > 0 && current_price < self.min_price {
+            Some(DcaSkipReason::PriceBelowMin)
+        } else {
+            None
+        }
+    }
+
+    /// Check if executions need the input token's price
+    pub fn uses_input_price(&self) -> bool {
+        (self.has_price_guard() && self.side() == DcaSide::Sell)
+            || self.strategy() == DcaStrategy::ValueAveraging
+    }
+
+    /// Check if executions need the output token's price
+    pub fn uses_output_price(&self) -> bool {
+        (self.has_price_guard() && self.side() == DcaSide::Buy)
+            || self.strategy() == DcaStrategy::ValueAveraging
+    }
+
+    /// Price the guard applies to: the token being bought, or the one being sold
+    pub fn guarded_price(&self, input_price: u64, output_price: u64) -> u64 {
+        match self.side() {
+            DcaSide::Buy => output_price,
+            DcaSide::Sell => input_price,
+        }
+    }
+
+    /// Check if the vault opted into matching against opposing vaults
+    pub fn is_matchable(&self) -> bool {
+        self.max_slippage_bps > 0
+            && self.strategy() == DcaStrategy::FixedAmount
+            && self.input_feed_id != [0u8; 32]
+            && self.output_feed_id != [0u8; 32]
+    }
+
+    /// Check if `other` trades the opposite direction of the same pair,
+    /// priced by the same feeds, and both vaults opted into matching
+    pub fn can_match(&self, other: &DcaVault) -> bool {
+        self.input_mint == other.output_mint
+            && self.output_mint == other.input_mint
+            && self.is_matchable()
+            && other.is_matchable()
+            && self.input_feed_id == other.output_feed_id
+            && self.output_feed_id == other.input_feed_id
+    }
+
+    /// USD value (6 decimals) of `amount` input tokens at `input_price`
+    pub fn input_value(&self, amount: u64, input_price: u64) -> Option<u64> {
+        let value = amount as u128 * input_price as u128 / 10u128.pow(self.input_decimals as u32);
+        u64::try_from(value).ok()
+    }
+
+    /// USD value (6 decimals) of `amount` output tokens at `output_price`
+    pub fn output_value(&self, amount: u64, output_price: u64) -> Option<u64> {
+        let value = amount as u128 * output_price as u128 / 10u128.pow(self.output_decimals as u32);
+        u64::try_from(value).ok()
+    }
+
+    /// Check that receiving `value_received` for `value_given` stays within
+    /// the vault's slippage limit
+    pub fn within_slippage(&self, value_given: u64, value_received: u64) -> bool {
+        value_received as u128 * 10_000
+            >= value_given as u128 * (10_000 - self.max_slippage_bps.min(10_000) as u128)
+    }
+
+    /// USD value of everything bought so far (6 decimals)
+    pub fn position_value(&self, output_price: u64) -> Option<u64> {
+        let value = self.total_received as u128 * output_price as u128
+            / 10u128.pow(self.output_decimals as u32);
+        u64::try_from(value).ok()
+    }
+
+    /// Length of a value averaging period: the average gap between executions
+    pub fn period_seconds(&self) -> i64 {
+        base_interval(self.min_executions, self.max_executions)
+    }
+
+    /// Value averaging periods started by `current_time`, counting the one
+    /// that began at creation; skipped executions still advance the target
+    pub fn periods_elapsed(&self, current_time: i64) -> u64 {
+        let elapsed = current_time.saturating_sub(self.created_at).max(0);
+        (elapsed / self.period_seconds()) as u64 + 1
+    }
+
+    /// Input to spend at `current_time` to reach the value averaging target,
+    /// bounded by the trade limits (0 when already at or above target)
+    pub fn value_averaging_amount(
+        &self,
+        input_price: u64,
+        output_price: u64,
+        current_time: i64,
+    ) -> Option<u64> {
+        if input_price == 0 {
+            return None;
+        }
+
+        let target_value =
+            self.periods_elapsed(current_time) as u128 * self.target_increment as u128;
+        let current_value = self.position_value(output_price)? as u128;
+        if current_value >= target_value {
+            return Some(0);
+        }
+
+        let shortfall = target_value - current_value;
+        let amount = shortfall * 10u128.pow(self.input_decimals as u32) / input_price as u128;
+        let amount = amount.clamp(self.min_trade_amount as u128, self.max_trade_amount as u128);
+        u64::try_from(amount).ok()
+    }
+
+    /// Randomize the next execution time after `current_time`
+    pub fn schedule_next_execution(&mut self, current_time: i64) {
+        self.next_execution =
+            randomized_next_execution(current_time, self.min_executions, self.max_executions);
+    }
+}
+
+/// Randomized next execution time for a schedule of `min..=max` executions per week
+pub fn randomized_next_execution(current_time: i64, min_executions: u8, max_executions: u8) -> i64 {
+    let base_interval = base_interval(min_executions, max_executions);
+    
+    // Add randomness based on timestamp
+    let random_offset = (current_time % 1000) * base_interval / 4000;
+    let next_interval = if current_time % 2 == 0 {
+        base_interval + random_offset
+    } else {
+        base_interval - random_offset
+    };
+    
+    current_time + next_interval.max(3600) // minimum 1 hour
+}
+
+/// Average seconds between executions for `min..=max` executions per week
+fn base_interval(min_executions: u8, max_executions: u8) -> i64 {
+    let avg_executions = (min_executions as u16 + max_executions as u16) / 2;
+    604800i64 / (avg_executions as i64).max(1) // seconds in week / executions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serialize(vault: &DcaVault) -> Vec<u8> {
+        let mut data = DcaVault::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(vault));
+        data
+    }
+
+    fn deserialize(data: &[u8]) -> DcaVault {
+        assert!(data.starts_with(DcaVault::DISCRIMINATOR));
+        bytemuck::pod_read_unaligned(&data[8..DcaVault::SPACE])
+    }
+
+    /// Matchable vault spending `input` (6 decimals) for `output` (9 decimals)
+    fn matchable(input: u8, output: u8) -> DcaVault {
+        DcaVault {
+            input_mint: Pubkey::new_from_array([input; 32]),
+            output_mint: Pubkey::new_from_array([output; 32]),
+            input_feed_id: [input; 32],
+            output_feed_id: [output; 32],
+            input_decimals: 6,
+            output_decimals: 9,
+            max_slippage_bps: 100,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn matches_only_opposing_vaults() {
+        let usdc_to_sol = matchable(1, 2);
+        let sol_to_usdc = DcaVault {
+            input_decimals: 9,
+            output_decimals: 6,
+            ..matchable(2, 1)
+        };
+        assert!(usdc_to_sol.is_matchable());
+        assert!(usdc_to_sol.can_match(&sol_to_usdc) && sol_to_usdc.can_match(&usdc_to_sol));
+
+        // Same direction, or a different pair
+        assert!(!usdc_to_sol.can_match(&usdc_to_sol));
+        assert!(!usdc_to_sol.can_match(&matchable(3, 1)));
+        assert!(!usdc_to_sol.can_match(&matchable(2, 3)));
+
+        // Opposing mints priced by other feeds
+        let other_feed = DcaVault { output_feed_id: [9; 32], ..sol_to_usdc };
+        assert!(!usdc_to_sol.can_match(&other_feed));
+
+        // Either side not opted in: no slippage limit, value averaging or unpriced
+        let opted_out = DcaVault { max_slippage_bps: 0, ..sol_to_usdc };
+        assert!(!opted_out.is_matchable() && !usdc_to_sol.can_match(&opted_out));
+        let averaging = DcaVault { strategy: DcaStrategy::ValueAveraging as u8, ..sol_to_usdc };
+        assert!(!averaging.is_matchable() && !usdc_to_sol.can_match(&averaging));
+        let unpriced = DcaVault { input_feed_id: [0; 32], ..sol_to_usdc };
+        assert!(!unpriced.is_matchable());
+    }
+
+    #[test]
+    fn values_both_sides_of_a_match() {
+        let usdc_to_sol = matchable(1, 2);
+        // 150 USDC and 1 SOL at $150
+        assert_eq!(usdc_to_sol.input_value(150_000_000, 1_000_000), Some(150_000_000));
+        assert_eq!(usdc_to_sol.output_value(1_000_000_000, 150_000_000), Some(150_000_000));
+        assert_eq!(usdc_to_sol.output_value(1, 150_000_000), Some(0));
+        assert_eq!(usdc_to_sol.input_value(u64::MAX, u64::MAX), None);
+    }
+
+    #[test]
+    fn slippage_applies_to_each_side() {
+        // A tolerates 1%, B only 0.5%
+        let a = matchable(1, 2);
+        let b = DcaVault { max_slippage_bps: 50, ..matchable(2, 1) };
+
+        // A gives $100 for $99: fine for A, and B (gaining) is happy
+        assert!(a.within_slippage(100_000_000, 99_000_000));
+        assert!(b.within_slippage(99_000_000, 100_000_000));
+        assert!(!a.within_slippage(100_000_000, 98_999_999));
+
+        // B gives $100 for $99.4: within A's 1% but past B's 0.5%
+        assert!(!b.within_slippage(100_000_000, 99_400_000));
+        assert!(b.within_slippage(100_000_000, 99_500_000));
+
+        // Limits past 100% never underflow
+        let reckless = DcaVault { max_slippage_bps: u16::MAX, ..a };
+        assert!(reckless.within_slippage(100, 0));
+    }
+
+    #[test]
+    fn price_guard_bounds_are_inclusive() {
+        let vault = DcaVault { min_price: 100_000_000, max_price: 200_000_000, ..Default::default() };
+        assert!(vault.has_price_guard());
+        assert_eq!(vault.check_price_guard(100_000_000), None);
+        assert_eq!(vault.check_price_guard(200_000_000), None);
+        assert_eq!(vault.check_price_guard(99_999_999), Some(DcaSkipReason::PriceBelowMin));
+        assert_eq!(vault.check_price_guard(200_000_001), Some(DcaSkipReason::PriceAboveMax));
+
+        // Each bound applies on its own
+        let max_only = DcaVault { min_price: 0, ..vault };
+        assert_eq!(max_only.check_price_guard(1), None);
+        assert_eq!(max_only.check_price_guard(200_000_001), Some(DcaSkipReason::PriceAboveMax));
+        let min_only = DcaVault { max_price: 0, ..vault };
+        assert_eq!(min_only.check_price_guard(u64::MAX), None);
+        assert_eq!(min_only.check_price_guard(99_999_999), Some(DcaSkipReason::PriceBelowMin));
+    }
+
+    #[test]
+    fn unguarded_vault_never_skips() {
+        let vault = DcaVault::default();
+        assert!(!vault.has_price_guard());
+        assert!(!vault.uses_input_price() && !vault.uses_output_price());
+        for price in [0, 1, u64::MAX] {
+            assert_eq!(vault.check_price_guard(price), None);
+        }
+    }
+
+    #[test]
+    fn guard_watches_the_traded_token() {
+        let buy = DcaVault { max_price: 200_000_000, ..Default::default() };
+        assert!(buy.uses_output_price() && !buy.uses_input_price());
+        assert_eq!(buy.guarded_price(1_000_000, 150_000_000), 150_000_000);
+
+        let sell = DcaVault { side: DcaSide::Sell as u8, ..buy };
+        assert!(sell.uses_input_price() && !sell.uses_output_price());
+        assert_eq!(sell.guarded_price(1_000_000, 150_000_000), 1_000_000);
+    }
+
+    /// ValueAveraging vault adding $100 per period, one period per day
+    fn value_averaging() -> DcaVault {
+        DcaVault {
+            strategy: DcaStrategy::ValueAveraging as u8,
+            created_at: 1_000_000,
+            min_executions: 7,
+            max_executions: 7,
+            target_increment: 100_000_000,
+            min_trade_amount: 10_000_000,
+            max_trade_amount: 500_000_000,
+            input_decimals: 6,
+            output_decimals: 9,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn value_averaging_targets_elapsed_periods() {
+        let mut vault = value_averaging();
+        assert_eq!(vault.period_seconds(), 86_400);
+        assert_eq!(vault.periods_elapsed(1_000_000), 1);
+        assert_eq!(vault.periods_elapsed(1_000_000 + 86_399), 1);
+        assert_eq!(vault.periods_elapsed(1_000_000 + 3 * 86_400), 4);
+        assert_eq!(vault.periods_elapsed(0), 1);
+
+        // First period: buy $100 of SOL at $150 with USDC at $1
+        assert_eq!(vault.value_averaging_amount(1_000_000, 150_000_000, 1_000_000), Some(100_000_000));
+
+        // Two skipped periods later the target is $300, not $200
+        vault.total_received = 666_666_667;
+        vault.execution_count = 1;
+        let later = 1_000_000 + 2 * 86_400;
+        assert_eq!(vault.value_averaging_amount(1_000_000, 150_000_000, later), Some(200_000_000));
+
+        // Already above target: nothing to buy
+        assert_eq!(vault.value_averaging_amount(1_000_000, 600_000_000, later), Some(0));
+        assert_eq!(vault.value_averaging_amount(0, 150_000_000, later), None);
+    }
+
+    #[test]
+    fn value_averaging_respects_trade_limits() {
+        let mut vault = value_averaging();
+
+        // A $1 shortfall is raised to the minimum trade
+        vault.total_received = 660_000_000;
+        assert_eq!(vault.value_averaging_amount(1_000_000, 150_000_000, 1_000_000), Some(10_000_000));
+
+        // A large shortfall is capped at the maximum trade
+        vault.total_received = 0;
+        let much_later = 1_000_000 + 30 * 86_400;
+        assert_eq!(vault.value_averaging_amount(1_000_000, 150_000_000, much_later), Some(500_000_000));
+    }
+
+    #[test]
+    fn space_matches_layout() {
+        assert_eq!(serialize(&DcaVault::default()).len(), DcaVault::SPACE);
+        assert_eq!(DcaVault::SPACE, 416);
+        assert_eq!(core::mem::align_of::<DcaVault>(), 8);
+    }
+
+    #[test]
+    fn memcmp_offsets_match_layout() {
+        // Offsets are part of the keeper's RPC filters and must never move
+        assert_eq!(DcaVault::NEXT_EXECUTION_OFFSET, 208);
+        assert_eq!(DcaVault::IS_ACTIVE_OFFSET, 340);
+
+        let mut vault = DcaVault {
+            next_execution: 0x0102_0304_0506_0708,
+            ..Default::default()
+        };
+        vault.set_active(true);
+        let data = serialize(&vault);
+        assert_eq!(data[DcaVault::IS_ACTIVE_OFFSET], 1);
+        assert_eq!(
+            data[DcaVault::NEXT_EXECUTION_OFFSET..DcaVault::NEXT_EXECUTION_OFFSET + 8],
+            vault.next_execution.to_le_bytes()
+        );
+
+        // Nothing else moved
+        let changed = serialize(&DcaVault::default())
+            .iter()
+            .zip(&data)
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(changed, 9);
+    }
+
+    #[test]
+    fn migrates_baseline_layout() {
+        let fixture = include_bytes!("../../tests/fixtures/dca_vault_v0_baseline.bin");
+        assert_eq!(fixture.len(), 302);
+
+        let vault = DcaVault::from_legacy(fixture).unwrap();
+        assert_eq!(vault.version, DcaVault::LAYOUT_VERSION);
+        assert_eq!(vault.authority, Pubkey::new_from_array([1; 32]));
+        assert_eq!(vault.output_vault, Pubkey::new_from_array([5; 32]));
+        assert_eq!(vault.total_amount, 1_000_000_000);
+        assert_eq!(vault.total_spent, 30_000_000);
+        assert_eq!(vault.execution_count, 3);
+        assert_eq!(vault.next_execution, 1_700_090_000);
+        assert!(vault.is_active());
+        assert_eq!(vault.output_vault_bump, 252);
+
+        // Fields added since the baseline come up as their defaults
+        assert_eq!(vault.output_feed_id, [0; 32]);
+        assert!(vault.strategy() == DcaStrategy::FixedAmount);
+        assert!(vault.side() == DcaSide::Buy);
+        assert_eq!(vault.max_slippage_bps, 0);
+        assert!(vault.privacy_level() == PrivacyLevel::Public);
+
+        // The converted account round-trips and cannot be migrated twice
+        let data = serialize(&vault);
+        assert_eq!(deserialize(&data).total_spent, 30_000_000);
+        assert!(DcaVault::from_legacy(&data).is_err());
+    }
+
+    #[test]
+    fn migrates_unversioned_layout() {
+        let fixture = include_bytes!("../../tests/fixtures/dca_vault_v0_current.bin");
+        assert_eq!(fixture.len(), 349);
+
+        let vault = DcaVault::from_legacy(fixture).unwrap();
+        assert_eq!(vault.version, DcaVault::LAYOUT_VERSION);
+        assert_eq!(vault.total_received, 180_000);
+        assert_eq!(vault.output_feed_id, [8; 32]);
+        assert_eq!(vault.min_price, 100_000_000);
+        assert!(vault.strategy() == DcaStrategy::ValueAveraging);
+        assert_eq!(vault.input_feed_id, [9; 32]);
+        assert_eq!(vault.max_trade_amount, 20_000_000);
+        assert_eq!(vault.output_decimals, 9);
+        assert_eq!(vault.max_slippage_bps, 50);
+        assert!(vault.privacy_level() == PrivacyLevel::Private);
+    }
+
+    #[test]
+    fn migrates_borsh_layout() {
+        let fixture = include_bytes!("../../tests/fixtures/dca_vault_v1_borsh.bin");
+        assert_eq!(fixture.len(), LegacyDcaVault::SPACE);
+
+        let vault = DcaVault::from_legacy(fixture).unwrap();
+        assert_eq!(vault.version, DcaVault::LAYOUT_VERSION);
+        assert_eq!(vault.authority, Pubkey::new_from_array([1; 32]));
+        assert_eq!(vault.next_execution, 1_700_090_000);
+        assert!(vault.is_active());
+        assert_eq!(vault.variance_bps, 2000);
+        assert_eq!((vault.min_executions, vault.max_executions), (3, 7));
+        assert_eq!((vault.window_start_hour, vault.window_end_hour), (8, 20));
+        assert_eq!(vault.target_increment, 50_000_000);
+        assert!(vault.side() == DcaSide::Buy);
+        assert!(vault.privacy_level() == PrivacyLevel::Private);
+        assert_eq!(vault.stats_counted, 0);
+        assert_eq!(vault.reserved, [0; 8]);
+    }
+
+    #[test]
+    fn stats_count_is_taken_once() {
+        let mut vault = DcaVault { stats_counted: 1, ..Default::default() };
+        assert!(vault.take_stats_count());
+        assert!(!vault.take_stats_count());
+        assert!(!DcaVault::default().take_stats_count());
+    }
 }
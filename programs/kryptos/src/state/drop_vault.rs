@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+/// How the drop's claim key is handed to the recipient
+///
+/// A client-side hint only: the program stores it for apps rendering the
+/// drop but never reads it. Either way `claim_lock` is an ed25519 public key
+/// and the claim transaction must carry its signature over
+/// `drop_vault || claimant` (see `claim_message`), so an observed claim
+/// cannot be replayed to another wallet.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropLockType {
+    /// The key is derived from a secret shared in the link: its 32-byte
+    /// ed25519 seed is the sha256 hash of the secret
+    #[default]
+    Secret,
+    /// The key is an ed25519 key shared with the recipient directly
+    ClaimKey,
+}
+
+#[account]
+#[derive(Default)]
+pub struct DropVault {
+    // === Owner ===
+    /// User who funded the drop (receives refunds and rent)
+    pub creator: Pubkey,
+
+    // === Token Config ===
+    /// Token being dropped
+    pub mint: Pubkey,
+    /// Token account vault holding the drop
+    pub token_vault: Pubkey,
+    /// Amount locked in the drop
+    pub amount: u64,
+
+    // === Lock ===
+    /// How the claim key was shared (client hint, not validated on-chain)
+    pub lock_type: DropLockType,
+    /// Claim public key (derived from the link secret for `Secret` drops)
+    pub claim_lock: [u8; 32],
+
+    // === Timing ===
+    /// Timestamp after which the drop can be refunded
+    pub expires_at: i64,
+    /// Creation timestamp
+    pub created_at: i64,
+
+    // === PDA Bumps ===
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl DropVault {
+    /// Account space (8 discriminator + fields)
+    pub const SPACE: usize = 8 +  // discriminator
+        32 +    // creator
+        32 +    // mint
+        32 +    // token_vault
+        8 +     // amount
+        1 +     // lock_type
+        32 +    // claim_lock
+        8 +     // expires_at
+        8 +     // created_at
+        1 +     // bump
+        1 +     // vault_bump
+        32;     // padding for future use
+
+    /// PDA seeds prefix
+    pub const SEED_PREFIX: &'static [u8] = b"drop_vault";
+
+    /// Default claim window before a drop can be refunded (7 days)
+    pub const DEFAULT_EXPIRY_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+    /// Longest claim window accepted (30 days)
+    pub const MAX_EXPIRY_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+    /// Check if the claim window has passed
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time >= self.expires_at
+    }
+
+    /// Message the drop's claim key signs to release it to `claimant`
+    pub fn claim_message(drop_vault: &Pubkey, claimant: &Pubkey) -> [u8; 64] {
+        let mut message = [0u8; 64];
+        message[..32].copy_from_slice(drop_vault.as_ref());
+        message[32..].copy_from_slice(claimant.as_ref());
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claim_message_binds_drop_and_claimant() {
+        let drop_vault = Pubkey::new_from_array([1; 32]);
+        let claimant = Pubkey::new_from_array([2; 32]);
+
+        let message = DropVault::claim_message(&drop_vault, &claimant);
+        assert_eq!(message[..32], drop_vault.to_bytes());
+        assert_eq!(message[32..], claimant.to_bytes());
+
+        // A signature for one claimant or drop is useless for any other
+        let other = Pubkey::new_from_array([3; 32]);
+        assert_ne!(message, DropVault::claim_message(&drop_vault, &other));
+        assert_ne!(message, DropVault::claim_message(&other, &claimant));
+        assert_ne!(message, DropVault::claim_message(&claimant, &drop_vault));
+    }
+
+    #[test]
+    fn expires_at_deadline() {
+        let drop_vault = DropVault { expires_at: 100, ..Default::default() };
+        assert!(!drop_vault.is_expired(99));
+        assert!(drop_vault.is_expired(100));
+    }
+}
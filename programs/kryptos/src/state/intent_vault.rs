@@ -1,25 +1,46 @@
 use anchor_lang::prelude::*;
 
-/// Intent type enumeration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
+use crate::entropy;
+use crate::errors::KryptosError;
+use crate::oracle::{self, OraclePrice, PRICE_DECIMALS};
+use crate::state::PrivacyLevel;
+
+/// Intent type enumeration (decides which price the trigger watches)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
 pub enum IntentType {
+    /// Trigger on the output token's USD price
     #[default]
     Buy,
+    /// Trigger on the input token's USD price
     Sell,
+    /// Trigger on the input token priced in output tokens, with
+    /// `rate_decimals` decimals (as in `TriggerMode::CrossRate`)
     Swap,
 }
 
 /// Trigger type enumeration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
 pub enum TriggerType {
     #[default]
     PriceAbove,
     PriceBelow,
     PriceRange,
+    /// Triggers once price falls `trail_bps` below the highest observed price
+    TrailingStop { trail_bps: u16 },
+}
+
+/// Trigger mode enumeration (what `trigger_price` is denominated in)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum TriggerMode {
+    /// USD price resolved by intent type (6 decimals)
+    #[default]
+    UsdPrice,
+    /// Input token priced in output tokens, with `rate_decimals` decimals
+    CrossRate,
 }
 
 /// Execution style enumeration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
 pub enum ExecutionStyle {
     #[default]
     Immediate,
@@ -27,8 +48,42 @@ pub enum ExecutionStyle {
     Twap,
 }
 
+/// Trigger parameters of a shielded intent, revealed by the keeper at execution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TriggerReveal {
+    /// PriceAbove, PriceBelow or PriceRange (trailing stops cannot be shielded)
+    pub trigger_type: TriggerType,
+    /// Trigger price, in the intent's trigger mode
+    pub trigger_price: u64,
+    /// For PriceRange: upper bound
+    pub trigger_price_max: u64,
+    /// Random salt chosen by the owner so the commitment cannot be brute-forced
+    pub salt: [u8; 32],
+}
+
+impl TriggerReveal {
+    /// Commitment stored on a shielded intent:
+    /// `sha256(trigger_type u8 || trigger_price u64 LE || trigger_price_max u64 LE || salt)`,
+    /// with trigger types coded PriceAbove = 0, PriceBelow = 1, PriceRange = 2
+    pub fn commitment(&self) -> Option<[u8; 32]> {
+        let code: u8 = match self.trigger_type {
+            TriggerType::PriceAbove => 0,
+            TriggerType::PriceBelow => 1,
+            TriggerType::PriceRange => 2,
+            TriggerType::TrailingStop { .. } => return None,
+        };
+        let hash = solana_sha256_hasher::hashv(&[
+            &[code],
+            &self.trigger_price.to_le_bytes(),
+            &self.trigger_price_max.to_le_bytes(),
+            &self.salt,
+        ]);
+        Some(hash.to_bytes())
+    }
+}
+
 /// Intent status enumeration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default, InitSpace)]
 pub enum IntentStatus {
     #[default]
     Monitoring,
@@ -40,7 +95,7 @@ pub enum IntentStatus {
 }
 
 #[account]
-#[derive(Default)]
+#[derive(Default, InitSpace)]
 pub struct IntentVault {
     // === Owner ===
     pub authority: Pubkey,
@@ -85,43 +140,136 @@ pub struct IntentVault {
     // === Bumps ===
     pub bump: u8,
     pub vault_bump: u8,
+
+    // === Oracle ===
+    /// Pyth feed id of the output token (zero = keeper-reported price)
+    pub output_feed_id: [u8; 32],
+
+    // === Trailing Stop ===
+    /// Highest oracle price observed so far (6 decimals)
+    pub peak_price: u64,
+    /// Timestamp of the last peak update
+    pub peak_updated_at: i64,
+
+    // === Chunk Timing ===
+    /// For Stealth/TWAP: interval between chunks (seconds)
+    pub chunk_interval: u32,
+    /// For Stealth: size and gap variance in basis points
+    pub chunk_variance_bps: u16,
+    /// Earliest timestamp for the next chunk
+    pub next_chunk_at: i64,
+
+    /// Pyth feed id of the input token (zero = keeper-reported price)
+    pub input_feed_id: [u8; 32],
+
+    // === Cross Rate ===
+    /// What the trigger prices are denominated in
+    pub trigger_mode: TriggerMode,
+    /// For CrossRate and Swap: decimals of the trigger prices
+    pub rate_decimals: u8,
+
+    // === Shielded Trigger ===
+    /// Hash of the hidden trigger parameters (zero = cleartext trigger)
+    pub trigger_commitment: [u8; 32],
+
+    // === Privacy ===
+    /// What logs and events reveal about the intent
+    pub privacy_level: PrivacyLevel,
+
+    // === Versioning ===
+    /// Layout version (0 = written before versioning, see `migrate`)
+    pub version: u8,
+
+    // === Stealth Sizing ===
+    /// For Stealth: size of the next non-final chunk, drawn on-chain
+    /// (0 = drawn before sizing existed, the base chunk size applies)
+    pub next_chunk_amount: u64,
+
+    // === Stats ===
+    /// Whether the intent is counted as active in its input mint's stats
+    /// shard (see `take_stats_count()`); cleared once it fully executes, is
+    /// cancelled or closed, and never set for intents created before shards
+    pub stats_counted: bool,
+    /// Unused bytes keeping `reserved` in whole words (always zero)
+    pub padding: [u8; 7],
+    /// Reserved for future fields (always zero)
+    pub reserved: [u64; 6],
 }
 
 impl IntentVault {
-    /// Account space
-    pub const SPACE: usize = 8 +  // discriminator
-        32 +    // authority
-        8 +     // nonce
-        1 +     // intent_type
-        32 +    // input_mint
-        32 +    // output_mint
-        32 +    // input_vault
-        8 +     // amount
-        1 +     // trigger_type
-        8 +     // trigger_price
-        8 +     // trigger_price_max
-        1 +     // execution_style
-        1 +     // num_chunks
-        1 +     // chunks_executed
-        8 +     // expires_at
-        8 +     // triggered_at
-        8 +     // executed_at
-        8 +     // created_at
-        1 +     // status
-        8 +     // total_spent
-        8 +     // total_received
-        1 +     // bump
-        1 +     // vault_bump
-        64;     // padding
+    /// Account space (8 discriminator + fields)
+    pub const SPACE: usize = 8 + Self::INIT_SPACE;
 
     /// PDA seeds prefix
     pub const SEED_PREFIX: &'static [u8] = b"intent_vault";
+
+    /// Byte offsets for RPC `memcmp` filters. Only fields before
+    /// `trigger_type` have one: `TriggerType::TrailingStop` stores its trail
+    /// distance inline, shifting every later field by 2 bytes.
+    pub const AUTHORITY_OFFSET: usize = 8;
+    pub const INTENT_TYPE_OFFSET: usize = 48;
+    pub const INPUT_MINT_OFFSET: usize = 49;
+    pub const OUTPUT_MINT_OFFSET: usize = 81;
+
+    /// Layout version written by this program (v2: Swap intents read
+    /// `rate_decimals` instead of a fixed 6-decimal rate)
+    pub const LAYOUT_VERSION: u8 = 2;
+
+    /// First version whose Swap intents honor `rate_decimals`
+    const SWAP_RATE_DECIMALS_VERSION: u8 = 2;
+
+    /// Upgrade an intent written by an earlier layout in place
+    ///
+    /// `data` must already be grown to `SPACE`. Every layout so far only
+    /// appended fields (the enums kept their `u8` codes), so the zero-filled
+    /// tail decodes as their defaults. Swap intents from before v2 get the
+    /// 6 decimals their trigger price was written in as `rate_decimals`.
+    pub fn migrate(data: &mut [u8]) -> Result<Self> {
+        let mut vault = Self::try_deserialize(&mut &data[..])?;
+        require!(
+            vault.version < Self::LAYOUT_VERSION,
+            KryptosError::VaultAlreadyMigrated
+        );
+
+        // Swap intents written before v2 compared against a 6-decimal rate
+        vault.rate_decimals = vault.trigger_rate_decimals();
+        vault.version = Self::LAYOUT_VERSION;
+        vault.try_serialize(&mut &mut data[..])?;
+        Ok(vault)
+    }
     
+    /// Stop counting the intent as active, returning whether its stats shard
+    /// must be decremented (true at most once per intent)
+    pub fn take_stats_count(&mut self) -> bool {
+        core::mem::take(&mut self.stats_counted)
+    }
+
     /// Check if intent has expired
     pub fn is_expired(&self, current_time: i64) -> bool {
         current_time > self.expires_at
     }
     
+    /// Amount still to be swapped
+    pub fn remaining_amount(&self) -> u64 {
+        self.amount.saturating_sub(self.total_spent)
+    }
+
+    /// Check if the full amount has been swapped
+    pub fn is_completed(&self) -> bool {
+        self.total_spent >= self.amount
+    }
+
+    /// Check if the next chunk is the last one and must sweep the remainder
+    pub fn is_final_chunk(&self) -> bool {
+        self.chunks_executed.saturating_add(1) >= self.num_chunks
+    }
+
+    /// Check if a withdrawal only refunds dust left after full execution,
+    /// keeping the `Executed` status instead of cancelling
+    pub fn withdraw_refunds_dust(&self) -> bool {
+        self.status == IntentStatus::Executed
+    }
+
     /// Check if trigger condition is met
     pub fn check_trigger(&self, current_price: u64) -> bool {
         match self.trigger_type {
@@ -130,6 +278,543 @@ impl IntentVault {
             TriggerType::PriceRange => {
                 current_price >= self.trigger_price && current_price <= self.trigger_price_max
             }
+            TriggerType::TrailingStop { .. } => {
+                self.peak_price > 0 && current_price <= self.trailing_stop_price()
+            }
+        }
+    }
+
+    /// Check if the trigger parameters are hidden behind a commitment
+    pub fn is_shielded(&self) -> bool {
+        self.trigger_commitment != [0u8; 32]
+    }
+
+    /// Check a keeper's reveal against the commitment and, if it matches,
+    /// load the trigger parameters so the trigger can be evaluated
+    pub fn reveal_trigger(&mut self, reveal: &TriggerReveal) -> bool {
+        if reveal.commitment() != Some(self.trigger_commitment) {
+            return false;
+        }
+
+        self.trigger_type = reveal.trigger_type;
+        self.trigger_price = reveal.trigger_price;
+        self.trigger_price_max = reveal.trigger_price_max;
+        true
+    }
+
+    /// Check if trigger prices must come from the oracle
+    pub fn has_price_feed(&self) -> bool {
+        self.input_feed_id != [0u8; 32] || self.output_feed_id != [0u8; 32]
+    }
+
+    /// Check if the trigger watches the input/output cross rate
+    pub fn uses_cross_rate(&self) -> bool {
+        self.trigger_mode == TriggerMode::CrossRate || self.intent_type == IntentType::Swap
+    }
+
+    /// Check if the trigger depends on the input token's price
+    pub fn uses_input_price(&self) -> bool {
+        self.uses_cross_rate() || self.intent_type == IntentType::Sell
+    }
+
+    /// Check if the trigger depends on the output token's price
+    pub fn uses_output_price(&self) -> bool {
+        self.uses_cross_rate() || self.intent_type == IntentType::Buy
+    }
+
+    /// Decimals of a cross-rate trigger price; Swap intents written before
+    /// v2 were priced at a fixed 6 decimals
+    pub fn trigger_rate_decimals(&self) -> u8 {
+        if self.intent_type == IntentType::Swap
+            && self.trigger_mode == TriggerMode::UsdPrice
+            && self.version < Self::SWAP_RATE_DECIMALS_VERSION
+        {
+            PRICE_DECIMALS as u8
+        } else {
+            self.rate_decimals
+        }
+    }
+
+    /// Resolve the price the trigger is evaluated against from USD prices
+    /// (6 decimals); Swap intents price the input token in output tokens.
+    /// Oracle-bound cross rates use `resolve_cross_rate` on the raw prices.
+    pub fn resolve_trigger_price(&self, input_price: u64, output_price: u64) -> Option<u64> {
+        match self.intent_type {
+            IntentType::Buy => Some(output_price),
+            IntentType::Sell => Some(input_price),
+            IntentType::Swap => self.resolve_cross_rate(
+                OraclePrice::from_usd(input_price),
+                OraclePrice::from_usd(output_price),
+            ),
+        }
+    }
+
+    /// Resolve a cross-rate trigger price (CrossRate mode or Swap): the input
+    /// token priced in output tokens with `trigger_rate_decimals` decimals
+    pub fn resolve_cross_rate(&self, input_price: OraclePrice, output_price: OraclePrice) -> Option<u64> {
+        oracle::cross_rate(input_price, output_price, self.trigger_rate_decimals())
+    }
+
+    /// Price at which a trailing stop fires (0 for other trigger types)
+    pub fn trailing_stop_price(&self) -> u64 {
+        match self.trigger_type {
+            TriggerType::TrailingStop { trail_bps } => {
+                (self.peak_price as u128 * (10_000 - trail_bps as u128) / 10_000) as u64
+            }
+            _ => 0,
+        }
+    }
+
+    /// Base chunk size for split executions
+    pub fn base_chunk_amount(&self) -> u64 {
+        self.amount / self.num_chunks.max(1) as u64
+    }
+
+    /// Check if the next chunk may execute now
+    pub fn chunk_ready(&self, current_time: i64) -> bool {
+        current_time >= self.next_chunk_at
+    }
+
+    /// Size the next non-final chunk must have
+    pub fn expected_chunk_amount(&self) -> u64 {
+        match self.execution_style {
+            ExecutionStyle::Stealth if self.next_chunk_amount > 0 => self.next_chunk_amount,
+            _ => self.base_chunk_amount(),
+        }
+    }
+
+    /// Check a non-final chunk's size against the execution style
+    pub fn valid_chunk_amount(&self, swap_amount: u64) -> bool {
+        match self.execution_style {
+            ExecutionStyle::Immediate => true,
+            ExecutionStyle::Twap | ExecutionStyle::Stealth => {
+                swap_amount == self.expected_chunk_amount()
+            }
+        }
+    }
+
+    /// Draw the next Stealth chunk's size within +/- variance of the base
+    /// size, leaving at least one unit for every chunk after it
+    pub fn draw_chunk_amount(&mut self, seed: &[u8; 32]) {
+        if self.execution_style != ExecutionStyle::Stealth {
+            return;
+        }
+
+        let base = self.base_chunk_amount();
+        let variance = (base as u128 * self.chunk_variance_bps as u128 / 10_000) as u64;
+        let chunks_after = self.num_chunks.saturating_sub(self.chunks_executed + 1) as u64;
+        let max_amount = self.remaining_amount().saturating_sub(chunks_after);
+        self.next_chunk_amount = entropy::jitter(base, variance, entropy::seed_u64(seed, 0))
+            .min(max_amount)
+            .max(1);
+    }
+
+    /// Schedule the next chunk after one executed at `current_time`; Stealth
+    /// draws its gap and size from `seed`
+    pub fn schedule_next_chunk(&mut self, current_time: i64, seed: &[u8; 32]) {
+        let interval = self.chunk_interval as u64;
+        let next_interval = match self.execution_style {
+            ExecutionStyle::Immediate => 0,
+            ExecutionStyle::Twap => interval,
+            ExecutionStyle::Stealth => {
+                // Randomize the gap within +/- variance of the interval
+                let max_offset = interval * self.chunk_variance_bps as u64 / 10_000;
+                entropy::jitter(interval, max_offset, entropy::seed_u64(seed, 1))
+            }
+        };
+
+        self.next_chunk_at = current_time + next_interval as i64;
+        self.draw_chunk_amount(seed);
+    }
+
+    /// Record a price observation for trailing stops, returning true if the peak moved
+    pub fn observe_price(&mut self, price: u64, observed_at: i64) -> bool {
+        if !matches!(self.trigger_type, TriggerType::TrailingStop { .. }) || price <= self.peak_price {
+            return false;
         }
+
+        self.peak_price = price;
+        self.peak_updated_at = observed_at;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent(intent_type: IntentType) -> IntentVault {
+        IntentVault {
+            intent_type,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn buy_resolves_output_price() {
+        let vault = intent(IntentType::Buy);
+        assert!(vault.uses_output_price() && !vault.uses_input_price());
+        assert_eq!(vault.resolve_trigger_price(1_000_000, 150_000_000), Some(150_000_000));
+    }
+
+    #[test]
+    fn sell_resolves_input_price() {
+        let vault = intent(IntentType::Sell);
+        assert!(vault.uses_input_price() && !vault.uses_output_price());
+        assert_eq!(vault.resolve_trigger_price(150_000_000, 1_000_000), Some(150_000_000));
+    }
+
+    #[test]
+    fn swap_resolves_cross_rate() {
+        let vault = intent(IntentType::Swap);
+        assert!(vault.uses_input_price() && vault.uses_output_price());
+        // JUP at $0.75 priced in SOL at $150 = 0.005 SOL
+        assert_eq!(vault.resolve_trigger_price(750_000, 150_000_000), Some(5_000));
+        assert_eq!(vault.resolve_trigger_price(750_000, 0), None);
+    }
+
+    #[test]
+    fn cross_rate_keeps_precision_for_small_prices() {
+        let vault = IntentVault {
+            trigger_mode: TriggerMode::CrossRate,
+            rate_decimals: 12,
+            ..intent(IntentType::Sell)
+        };
+        assert!(vault.uses_input_price() && vault.uses_output_price());
+
+        // BONK at $0.00002 priced in SOL at $150 = 0.000000133333 SOL
+        let bonk = OraclePrice { price: 200_000, exponent: -10 };
+        let sol = OraclePrice { price: 15_000_000_000, exponent: -8 };
+        assert_eq!(vault.resolve_cross_rate(bonk, sol), Some(133_333));
+    }
+
+    #[test]
+    fn swap_cross_rate_triggers() {
+        let mut vault = intent(IntentType::Swap);
+        vault.trigger_type = TriggerType::PriceAbove;
+        vault.trigger_price = 4_000;
+        let rate = vault.resolve_trigger_price(750_000, 150_000_000).unwrap();
+        assert!(vault.check_trigger(rate));
+    }
+
+    #[test]
+    fn swap_honors_rate_decimals() {
+        let vault = IntentVault {
+            rate_decimals: 12,
+            version: IntentVault::LAYOUT_VERSION,
+            ..intent(IntentType::Swap)
+        };
+        assert!(vault.uses_cross_rate());
+
+        // BONK at $0.00002 in SOL at $150 no longer truncates to 0
+        assert_eq!(vault.resolve_trigger_price(20, 150_000_000), Some(133_333));
+        let bonk = OraclePrice { price: 200_000, exponent: -10 };
+        let sol = OraclePrice { price: 15_000_000_000, exponent: -8 };
+        assert_eq!(vault.resolve_cross_rate(bonk, sol), Some(133_333));
+        assert_eq!(vault.resolve_trigger_price(750_000, 0), None);
+    }
+
+    #[test]
+    fn legacy_swap_keeps_six_decimal_rate() {
+        let legacy = IntentVault {
+            version: 1,
+            trigger_price: 4_000,
+            ..intent(IntentType::Swap)
+        };
+        assert_eq!(legacy.trigger_rate_decimals(), 6);
+        assert_eq!(legacy.resolve_trigger_price(750_000, 150_000_000), Some(5_000));
+
+        // Migrating pins the 6 decimals the trigger price was written in
+        let mut data = serialize(&legacy);
+        data.resize(IntentVault::SPACE, 0);
+        let migrated = IntentVault::migrate(&mut data).unwrap();
+        assert_eq!(migrated.rate_decimals, 6);
+        assert_eq!(migrated.trigger_rate_decimals(), 6);
+        assert_eq!(migrated.resolve_trigger_price(750_000, 150_000_000), Some(5_000));
+
+        // Other intents keep their own decimals
+        let cross = IntentVault {
+            version: 1,
+            trigger_mode: TriggerMode::CrossRate,
+            rate_decimals: 9,
+            ..intent(IntentType::Sell)
+        };
+        assert_eq!(cross.trigger_rate_decimals(), 9);
+        assert_eq!(intent(IntentType::Buy).trigger_rate_decimals(), 0);
+    }
+
+    #[test]
+    fn shielded_trigger_reveals_only_matching_params() {
+        let reveal = TriggerReveal {
+            trigger_type: TriggerType::PriceBelow,
+            trigger_price: 180_000_000,
+            trigger_price_max: 0,
+            salt: [7u8; 32],
+        };
+        let mut vault = IntentVault {
+            trigger_commitment: reveal.commitment().unwrap(),
+            ..intent(IntentType::Buy)
+        };
+        assert!(vault.is_shielded());
+
+        let wrong = TriggerReveal { trigger_price: 179_000_000, ..reveal };
+        assert!(!vault.reveal_trigger(&wrong));
+        assert_eq!(vault.trigger_price, 0);
+
+        assert!(vault.reveal_trigger(&reveal));
+        assert!(vault.trigger_type == TriggerType::PriceBelow);
+        assert!(vault.check_trigger(170_000_000));
+
+        let trailing = TriggerReveal {
+            trigger_type: TriggerType::TrailingStop { trail_bps: 500 },
+            ..reveal
+        };
+        assert_eq!(trailing.commitment(), None);
+    }
+
+    fn trailing(peak_price: u64) -> IntentVault {
+        IntentVault {
+            trigger_type: TriggerType::TrailingStop { trail_bps: 500 },
+            peak_price,
+            ..intent(IntentType::Sell)
+        }
+    }
+
+    #[test]
+    fn trailing_peak_only_rises() {
+        let mut vault = trailing(100_000_000);
+        assert!(vault.observe_price(120_000_000, 10));
+        assert_eq!((vault.peak_price, vault.peak_updated_at), (120_000_000, 10));
+        assert_eq!(vault.trailing_stop_price(), 114_000_000);
+
+        // Lower and equal prices leave the mark where it is
+        assert!(!vault.observe_price(110_000_000, 20));
+        assert!(!vault.observe_price(120_000_000, 30));
+        assert_eq!((vault.peak_price, vault.peak_updated_at), (120_000_000, 10));
+
+        // Other trigger types have no peak to move
+        let mut below = IntentVault { trigger_type: TriggerType::PriceBelow, ..trailing(0) };
+        assert!(!below.observe_price(120_000_000, 10));
+        assert_eq!(below.peak_price, 0);
+        assert_eq!(below.trailing_stop_price(), 0);
+    }
+
+    #[test]
+    fn trailing_stop_triggers_at_trail_below_peak() {
+        // 5% below a $100 peak
+        let vault = trailing(100_000_000);
+        assert_eq!(vault.trailing_stop_price(), 95_000_000);
+        assert!(vault.check_trigger(95_000_000));
+        assert!(vault.check_trigger(90_000_000));
+        assert!(!vault.check_trigger(95_000_001));
+
+        // Without a peak nothing triggers, not even a zero price
+        let unset = trailing(0);
+        assert!(!unset.check_trigger(0));
+        assert!(!unset.check_trigger(1));
+    }
+
+    #[test]
+    fn final_chunk_sweeps_remainder() {
+        let mut vault = IntentVault {
+            amount: 1_000,
+            execution_style: ExecutionStyle::Twap,
+            num_chunks: 3,
+            ..Default::default()
+        };
+        assert_eq!(vault.remaining_amount(), 1_000);
+        assert!(!vault.is_final_chunk() && !vault.is_completed());
+
+        // Two base chunks of 333, then the final chunk must take the 334 left
+        for _ in 0..2 {
+            assert!(vault.valid_chunk_amount(333));
+            vault.total_spent += 333;
+            vault.chunks_executed += 1;
+        }
+        assert!(vault.is_final_chunk());
+        assert_eq!(vault.remaining_amount(), 334);
+        assert!(!vault.is_completed());
+
+        vault.total_spent += 334;
+        vault.chunks_executed += 1;
+        assert!(vault.is_completed());
+        assert_eq!(vault.remaining_amount(), 0);
+
+        // Overspent accounting never underflows
+        vault.total_spent += 1;
+        assert!(vault.is_completed());
+        assert_eq!(vault.remaining_amount(), 0);
+    }
+
+    #[test]
+    fn withdraw_after_execution_refunds_dust() {
+        let mut vault = IntentVault { amount: 1_000, ..Default::default() };
+        assert!(!vault.withdraw_refunds_dust());
+
+        vault.total_spent = 1_000;
+        vault.status = IntentStatus::Executed;
+        assert!(vault.withdraw_refunds_dust());
+
+        vault.status = IntentStatus::Cancelled;
+        assert!(!vault.withdraw_refunds_dust());
+    }
+
+    fn chunked(execution_style: ExecutionStyle) -> IntentVault {
+        IntentVault {
+            amount: 1_000,
+            execution_style,
+            num_chunks: 4,
+            chunk_interval: 3_600,
+            chunk_variance_bps: 2_500,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn twap_splits_exactly() {
+        let mut vault = chunked(ExecutionStyle::Twap);
+        assert!(vault.valid_chunk_amount(250));
+        assert!(!vault.valid_chunk_amount(249) && !vault.valid_chunk_amount(251));
+
+        // TWAP ignores the seed: fixed size and fixed gap
+        vault.schedule_next_chunk(1_000, &[9; 32]);
+        assert_eq!(vault.next_chunk_at, 4_600);
+        assert_eq!(vault.expected_chunk_amount(), 250);
+        assert!(vault.chunk_ready(4_600) && !vault.chunk_ready(4_599));
+    }
+
+    #[test]
+    fn stealth_draws_size_and_gap_within_variance() {
+        let mut sizes = Vec::new();
+        for counter in 0..32u64 {
+            let seed = entropy::vault_seed(&[3; 32], &Pubkey::default(), counter);
+            let mut vault = chunked(ExecutionStyle::Stealth);
+            vault.schedule_next_chunk(1_000, &seed);
+
+            // +/- 25% of the 250 base size and the 3600 second interval
+            assert!((188..=312).contains(&vault.next_chunk_amount));
+            assert!((1_000 + 2_700..=1_000 + 4_500).contains(&vault.next_chunk_at));
+            assert!(vault.valid_chunk_amount(vault.next_chunk_amount));
+            assert!(!vault.valid_chunk_amount(vault.next_chunk_amount + 1));
+            sizes.push(vault.next_chunk_amount);
+        }
+        // The draw actually varies with the seed
+        sizes.dedup();
+        assert!(sizes.len() > 1);
+    }
+
+    #[test]
+    fn stealth_leaves_a_unit_for_every_later_chunk() {
+        let mut vault = chunked(ExecutionStyle::Stealth);
+        vault.chunk_variance_bps = 5_000;
+        vault.chunks_executed = 1;
+        vault.total_spent = 995;
+        vault.draw_chunk_amount(&[0xff; 32]);
+
+        // 5 left for the 2nd and 3rd chunks before the final one sweeps
+        assert_eq!(vault.remaining_amount(), 5);
+        assert_eq!(vault.next_chunk_amount, 3);
+
+        // Intents drawn before sizing existed fall back to the base size
+        let mut legacy = chunked(ExecutionStyle::Stealth);
+        legacy.next_chunk_amount = 0;
+        assert_eq!(legacy.expected_chunk_amount(), 250);
+    }
+
+    fn serialize(vault: &IntentVault) -> Vec<u8> {
+        let mut data = Vec::new();
+        vault.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn space_fits_largest_trigger_type() {
+        // Only TrailingStop uses the two bytes reserved for its trail distance
+        let trailing = IntentVault {
+            trigger_type: TriggerType::TrailingStop { trail_bps: 500 },
+            ..Default::default()
+        };
+        assert_eq!(serialize(&trailing).len(), IntentVault::SPACE);
+        assert_eq!(serialize(&IntentVault::default()).len(), IntentVault::SPACE - 2);
+        assert_eq!(IntentVault::SPACE, 420);
+    }
+
+    #[test]
+    fn field_offsets_match_layout() {
+        let mut vault = IntentVault {
+            authority: Pubkey::new_from_array([1; 32]),
+            intent_type: IntentType::Swap,
+            input_mint: Pubkey::new_from_array([2; 32]),
+            output_mint: Pubkey::new_from_array([3; 32]),
+            ..Default::default()
+        };
+
+        // The trail distance only moves fields after the trigger type
+        for trigger_type in [TriggerType::PriceBelow, TriggerType::TrailingStop { trail_bps: 500 }] {
+            vault.trigger_type = trigger_type;
+            let data = serialize(&vault);
+            let at = |offset: usize| &data[offset..offset + 32];
+            assert_eq!(at(IntentVault::AUTHORITY_OFFSET), [1; 32]);
+            assert_eq!(data[IntentVault::INTENT_TYPE_OFFSET], 2);
+            assert_eq!(at(IntentVault::INPUT_MINT_OFFSET), [2; 32]);
+            assert_eq!(at(IntentVault::OUTPUT_MINT_OFFSET), [3; 32]);
+        }
+    }
+
+    /// Grow a recorded account to `SPACE`, as `migrate_intent_vault` does
+    fn grown(fixture: &[u8]) -> Vec<u8> {
+        let mut data = fixture.to_vec();
+        data.resize(IntentVault::SPACE, 0);
+        data
+    }
+
+    #[test]
+    fn migrates_baseline_layout() {
+        let fixture = include_bytes!("../../tests/fixtures/intent_vault_v0_baseline.bin");
+        assert_eq!(fixture.len(), 288);
+        assert!(IntentVault::try_deserialize(&mut &fixture[..]).is_err());
+
+        let mut data = grown(fixture);
+        let vault = IntentVault::migrate(&mut data).unwrap();
+        assert_eq!(vault.version, IntentVault::LAYOUT_VERSION);
+        assert_eq!(vault.authority, Pubkey::new_from_array([6; 32]));
+        assert_eq!(vault.nonce, 42);
+        assert!(vault.intent_type == IntentType::Sell);
+        assert!(vault.trigger_type == TriggerType::PriceBelow);
+        assert_eq!(vault.trigger_price, 140_000_000);
+        assert!(vault.execution_style == ExecutionStyle::Twap);
+        assert_eq!(vault.chunks_executed, 1);
+        assert!(vault.status == IntentStatus::Executing);
+        assert_eq!(vault.total_received, 175_000_000);
+        assert_eq!(vault.vault_bump, 250);
+
+        // Fields added since the baseline come up as their defaults
+        assert!(!vault.has_price_feed());
+        assert_eq!(vault.chunk_interval, 0);
+        assert!(vault.trigger_mode == TriggerMode::UsdPrice);
+        assert!(!vault.is_shielded());
+        assert!(vault.privacy_level == PrivacyLevel::Public);
+        // Predates stats shards, so closing it leaves the counts alone
+        assert!(!vault.stats_counted);
+
+        // The upgraded account decodes directly and cannot be migrated twice
+        let decoded = IntentVault::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(decoded.version, IntentVault::LAYOUT_VERSION);
+        assert!(IntentVault::migrate(&mut data).is_err());
+    }
+
+    #[test]
+    fn migrates_unversioned_layout() {
+        let fixture = include_bytes!("../../tests/fixtures/intent_vault_v0_current.bin");
+        assert_eq!(fixture.len(), 355);
+
+        let vault = IntentVault::migrate(&mut grown(fixture)).unwrap();
+        assert_eq!(vault.version, IntentVault::LAYOUT_VERSION);
+        assert!(vault.trigger_type == TriggerType::TrailingStop { trail_bps: 500 });
+        assert_eq!(vault.peak_price, 150_000_000);
+        assert_eq!(vault.chunk_interval, 3600);
+        assert_eq!(vault.next_chunk_at, 1_700_103_600);
+        assert_eq!(vault.input_feed_id, [9; 32]);
+        assert!(vault.privacy_level == PrivacyLevel::Private);
     }
 }
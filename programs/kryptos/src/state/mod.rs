@@ -1,5 +1,25 @@
+pub mod basket_dca_vault;
+pub mod burn_receipt;
+pub mod dca_pool;
 pub mod dca_vault;
+pub mod drop_vault;
 pub mod intent_vault;
+pub mod privacy;
+pub mod protocol_stats;
+pub mod rebalance_vault;
+pub mod stream_vault;
+pub mod user_registry;
+pub mod vault_metadata;
 
+pub use basket_dca_vault::*;
+pub use burn_receipt::*;
+pub use dca_pool::*;
 pub use dca_vault::*;
+pub use drop_vault::*;
 pub use intent_vault::*;
+pub use privacy::*;
+pub use protocol_stats::*;
+pub use rebalance_vault::*;
+pub use stream_vault::*;
+pub use user_registry::*;
+pub use vault_metadata::*;
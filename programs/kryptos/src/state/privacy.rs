@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+/// How much a vault reveals in program logs and events
+///
+/// Account state always holds the exact values, so the owner keeps full
+/// visibility by reading the vault itself.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum PrivacyLevel {
+    /// Full logs and exact event data
+    #[default]
+    Public,
+    /// No `msg!` logs, coarsened event amounts and no schedule in events
+    Private,
+}
+
+impl PrivacyLevel {
+    /// Check if `msg!` logs may be written
+    pub fn logs_enabled(&self) -> bool {
+        *self == PrivacyLevel::Public
+    }
+
+    /// Amount as published in events
+    pub fn event_amount(&self, amount: u64) -> u64 {
+        match self {
+            PrivacyLevel::Public => amount,
+            PrivacyLevel::Private => coarsen_amount(amount),
+        }
+    }
+
+    /// The stricter of two levels, for events that reveal several vaults
+    pub fn stricter(self, other: PrivacyLevel) -> PrivacyLevel {
+        if self.logs_enabled() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Schedule timestamp as published in events (0 = withheld)
+    pub fn event_schedule(&self, timestamp: i64) -> i64 {
+        match self {
+            PrivacyLevel::Public => timestamp,
+            PrivacyLevel::Private => 0,
+        }
+    }
+}
+
+/// Round an amount down to its two most significant decimal digits
+pub fn coarsen_amount(amount: u64) -> u64 {
+    let mut scale = 1u64;
+    while amount / scale >= 100 {
+        scale *= 10;
+    }
+    amount / scale * scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coarsens_to_two_significant_digits() {
+        assert_eq!(coarsen_amount(0), 0);
+        assert_eq!(coarsen_amount(99), 99);
+        assert_eq!(coarsen_amount(123_456_789), 120_000_000);
+        assert_eq!(coarsen_amount(u64::MAX), 18_000_000_000_000_000_000);
+
+        assert_eq!(PrivacyLevel::Public.event_amount(123_456), 123_456);
+        assert_eq!(PrivacyLevel::Private.event_amount(123_456), 120_000);
+        assert_eq!(PrivacyLevel::Private.event_schedule(1_700_000_000), 0);
+    }
+
+    #[test]
+    fn stricter_level_wins() {
+        use PrivacyLevel::{Private, Public};
+        assert!(Public.stricter(Public) == Public);
+        assert!(Public.stricter(Private) == Private);
+        assert!(Private.stricter(Public) == Private);
+        assert!(Private.stricter(Private) == Private);
+    }
+}
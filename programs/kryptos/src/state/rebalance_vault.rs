@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+
+/// Maximum number of tokens held by a rebalancing vault
+pub const MAX_REBALANCE_ASSETS: usize = 5;
+
+/// One token of a rebalancing portfolio
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RebalanceAsset {
+    /// Token held
+    pub mint: Pubkey,
+    /// Token account holding this asset (default until opened)
+    pub vault: Pubkey,
+    /// Pyth feed id used to value this asset
+    pub feed_id: [u8; 32],
+    /// Target share of the portfolio value (basis points)
+    pub target_weight_bps: u16,
+    /// Mint decimals (recorded when the asset is opened)
+    pub decimals: u8,
+    /// Asset token account bump
+    pub vault_bump: u8,
+}
+
+impl RebalanceAsset {
+    pub const SPACE: usize = 32 +  // mint
+        32 +    // vault
+        32 +    // feed_id
+        2 +     // target_weight_bps
+        1 +     // decimals
+        1;      // vault_bump
+
+    /// Check if the asset token account has been created
+    pub fn is_open(&self) -> bool {
+        self.vault != Pubkey::default()
+    }
+
+    /// USD value (6 decimals) of `amount` at `price` (6 decimals USD)
+    pub fn value_of(&self, amount: u64, price: u64) -> Option<u64> {
+        let value = (amount as u128)
+            .checked_mul(price as u128)?
+            .checked_div(10u128.checked_pow(self.decimals as u32)?)?;
+        u64::try_from(value).ok()
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct RebalanceVault {
+    // === Owner ===
+    /// User who created this portfolio
+    pub authority: Pubkey,
+    /// Unique nonce to allow multiple portfolios per user
+    pub nonce: u64,
+
+    // === Portfolio ===
+    /// Number of configured assets
+    pub num_assets: u8,
+    /// Assets with their target weights
+    pub assets: [RebalanceAsset; MAX_REBALANCE_ASSETS],
+
+    // === Rebalance Config ===
+    /// Minimum drift of any asset from its target before rebalancing (basis points)
+    pub drift_threshold_bps: u16,
+    /// Maximum value lost per rebalancing swap, at oracle prices (basis points)
+    pub max_slippage_bps: u16,
+
+    // === Tracking ===
+    /// Number of rebalancing swaps executed
+    pub rebalance_count: u32,
+    /// Timestamp of last rebalancing swap
+    pub last_rebalance: i64,
+
+    // === Status ===
+    /// Whether the portfolio is still managed
+    pub is_active: bool,
+    /// Creation timestamp
+    pub created_at: i64,
+
+    // === PDA Bumps ===
+    pub bump: u8,
+}
+
+impl RebalanceVault {
+    /// Account space (8 discriminator + fields)
+    pub const SPACE: usize = 8 +  // discriminator
+        32 +    // authority
+        8 +     // nonce
+        1 +     // num_assets
+        RebalanceAsset::SPACE * MAX_REBALANCE_ASSETS + // assets
+        2 +     // drift_threshold_bps
+        2 +     // max_slippage_bps
+        4 +     // rebalance_count
+        8 +     // last_rebalance
+        1 +     // is_active
+        8 +     // created_at
+        1 +     // bump
+        64;     // padding for future use
+
+    /// PDA seeds prefix
+    pub const SEED_PREFIX: &'static [u8] = b"rebalance_vault";
+
+    /// Asset token account seeds prefix
+    pub const ASSET_SEED_PREFIX: &'static [u8] = b"rebalance_asset";
+
+    /// Configured assets
+    pub fn active_assets(&self) -> &[RebalanceAsset] {
+        &self.assets[..self.num_assets as usize]
+    }
+
+    /// Value `asset_index` should hold out of `total_value`
+    pub fn target_value(&self, asset_index: usize, total_value: u64) -> u64 {
+        (self.assets[asset_index].target_weight_bps as u128 * total_value as u128 / 10_000) as u64
+    }
+
+    /// Largest distance of any asset's weight from its target (basis points);
+    /// `values` holds one USD value per configured asset
+    pub fn max_drift_bps(&self, values: &[u64]) -> u64 {
+        let values = &values[..values.len().min(self.num_assets as usize)];
+        let total_value: u128 = values.iter().map(|value| *value as u128).sum();
+        if total_value == 0 {
+            return 0;
+        }
+
+        values
+            .iter()
+            .zip(self.active_assets())
+            .map(|(value, asset)| {
+                let weight_bps = (*value as u128 * 10_000 / total_value) as u64;
+                weight_bps.abs_diff(asset.target_weight_bps as u64)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(target_weight_bps: u16, decimals: u8) -> RebalanceAsset {
+        RebalanceAsset {
+            target_weight_bps,
+            decimals,
+            ..Default::default()
+        }
+    }
+
+    /// Portfolio with the given target weights (basis points)
+    fn portfolio(weights: &[u16]) -> RebalanceVault {
+        let mut vault = RebalanceVault {
+            num_assets: weights.len() as u8,
+            ..Default::default()
+        };
+        for (slot, weight_bps) in vault.assets.iter_mut().zip(weights) {
+            *slot = asset(*weight_bps, 6);
+        }
+        vault
+    }
+
+    #[test]
+    fn value_of_scales_by_decimals() {
+        // 2.5 SOL (9 decimals) at $150
+        assert_eq!(asset(0, 9).value_of(2_500_000_000, 150_000_000), Some(375_000_000));
+        // 1M BONK (5 decimals) at $0.00002
+        assert_eq!(asset(0, 5).value_of(100_000_000_000, 20), Some(20_000_000));
+        // Dust below one micro-dollar truncates to 0
+        assert_eq!(asset(0, 9).value_of(1, 150_000_000), Some(0));
+        assert_eq!(asset(0, 9).value_of(0, 150_000_000), Some(0));
+
+        // Values past u64 and absurd decimals fail instead of wrapping
+        assert_eq!(asset(0, 0).value_of(u64::MAX, 2), None);
+        assert_eq!(asset(0, 40).value_of(1, 1), None);
+    }
+
+    #[test]
+    fn max_drift_reports_furthest_asset() {
+        let vault = portfolio(&[5_000, 3_000, 2_000]);
+        assert_eq!(vault.max_drift_bps(&[500, 300, 200]), 0);
+
+        // 60/25/15: the first asset drifted 1000 bps, the most of the three
+        assert_eq!(vault.max_drift_bps(&[600, 250, 150]), 1_000);
+
+        // Underweight counts the same as overweight
+        assert_eq!(vault.max_drift_bps(&[500, 100, 400]), 2_000);
+
+        // One asset holding everything
+        assert_eq!(vault.max_drift_bps(&[0, 0, 1_000]), 8_000);
+    }
+
+    #[test]
+    fn max_drift_of_empty_portfolio_is_zero() {
+        let vault = portfolio(&[5_000, 5_000]);
+        assert_eq!(vault.max_drift_bps(&[0, 0]), 0);
+        assert_eq!(vault.max_drift_bps(&[]), 0);
+
+        // Values for unconfigured slots are ignored
+        assert_eq!(vault.max_drift_bps(&[500, 500, 1_000_000]), 0);
+        assert_eq!(vault.target_value(0, 1_000), 500);
+    }
+}
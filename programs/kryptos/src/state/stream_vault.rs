@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct StreamVault {
+    // === Parties ===
+    /// User funding the stream
+    pub sender: Pubkey,
+    /// User receiving the payments
+    pub recipient: Pubkey,
+    /// Unique nonce to allow multiple streams per sender/recipient
+    pub nonce: u64,
+
+    // === Token Config ===
+    /// Token being paid
+    pub mint: Pubkey,
+    /// Token account vault holding the unpaid balance
+    pub token_vault: Pubkey,
+
+    // === Schedule ===
+    /// Total amount streamed (in smallest unit)
+    pub total_amount: u64,
+    /// Amount vested by each payment (the last one vests the remainder)
+    pub amount_per_payment: u64,
+    /// Seconds between payments
+    pub interval_seconds: u32,
+    /// Timing variance per payment, as basis points of the interval (max 2500)
+    pub variance_bps: u16,
+    /// Seed of the per-payment timing jitter (drawn from the creating slot hash)
+    pub jitter_seed: u64,
+    /// Schedule start; payment `k` is due `k` intervals later (plus jitter)
+    pub start_time: i64,
+
+    // === Tracking ===
+    /// Amount claimed by the recipient so far
+    pub total_claimed: u64,
+
+    // === Status ===
+    /// Whether the stream is still running
+    pub is_active: bool,
+    /// Creation timestamp
+    pub created_at: i64,
+
+    // === PDA Bumps ===
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl StreamVault {
+    /// Account space (8 discriminator + fields)
+    pub const SPACE: usize = 8 +  // discriminator
+        32 +    // sender
+        32 +    // recipient
+        8 +     // nonce
+        32 +    // mint
+        32 +    // token_vault
+        8 +     // total_amount
+        8 +     // amount_per_payment
+        4 +     // interval_seconds
+        2 +     // variance_bps
+        8 +     // jitter_seed
+        8 +     // start_time
+        8 +     // total_claimed
+        1 +     // is_active
+        8 +     // created_at
+        1 +     // bump
+        1 +     // vault_bump
+        64;     // padding for future use
+
+    /// PDA seeds prefix
+    pub const SEED_PREFIX: &'static [u8] = b"stream_vault";
+
+    /// Maximum timing variance (keeps payments in schedule order)
+    pub const MAX_VARIANCE_BPS: u16 = 2500;
+
+    /// Number of payments in the schedule
+    pub fn num_payments(&self) -> u64 {
+        self.total_amount.div_ceil(self.amount_per_payment.max(1))
+    }
+
+    /// Due time of payment `index` (1-based)
+    pub fn payment_due_at(&self, index: u64) -> i64 {
+        let interval = self.interval_seconds as i64;
+        self.start_time + index as i64 * interval + self.payment_jitter(index)
+    }
+
+    /// Number of payments due at `current_time`
+    ///
+    /// Jitter stays within a quarter interval, so every payment before the
+    /// current interval is due and every payment after the next one is not;
+    /// only those two need checking.
+    pub fn vested_payments(&self, current_time: i64) -> u64 {
+        if current_time <= self.start_time || self.interval_seconds == 0 {
+            return 0;
+        }
+
+        let elapsed_intervals = ((current_time - self.start_time) / self.interval_seconds as i64) as u64;
+        let mut vested = elapsed_intervals.saturating_sub(1);
+        for index in elapsed_intervals.max(1)..=elapsed_intervals + 1 {
+            if self.payment_due_at(index) <= current_time {
+                vested += 1;
+            }
+        }
+
+        vested.min(self.num_payments())
+    }
+
+    /// Amount vested at `current_time`
+    pub fn vested_amount(&self, current_time: i64) -> u64 {
+        self.vested_payments(current_time)
+            .saturating_mul(self.amount_per_payment)
+            .min(self.total_amount)
+    }
+
+    /// Vested amount the recipient has not claimed yet
+    pub fn claimable_amount(&self, current_time: i64) -> u64 {
+        self.vested_amount(current_time).saturating_sub(self.total_claimed)
+    }
+
+    /// Deterministic timing offset of payment `index`, within
+    /// ±`variance_bps` of the interval
+    fn payment_jitter(&self, index: u64) -> i64 {
+        let span = self.interval_seconds as u64 * self.variance_bps as u64 / 10_000;
+        if span == 0 {
+            return 0;
+        }
+
+        // splitmix64 of the seed and payment index
+        let mut x = self.jitter_seed ^ index.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        x ^= x >> 31;
+
+        (x % (2 * span + 1)) as i64 - span as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10 payments of 100 every hour, starting at 1_000_000
+    fn stream(variance_bps: u16, jitter_seed: u64) -> StreamVault {
+        StreamVault {
+            total_amount: 1_000,
+            amount_per_payment: 100,
+            interval_seconds: 3_600,
+            variance_bps,
+            jitter_seed,
+            start_time: 1_000_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn vests_on_schedule_without_variance() {
+        let vault = stream(0, 42);
+        assert_eq!(vault.num_payments(), 10);
+        assert_eq!(vault.vested_payments(999_000), 0);
+        assert_eq!(vault.vested_payments(1_000_000), 0);
+        assert_eq!(vault.vested_payments(1_003_599), 0);
+        assert_eq!(vault.vested_payments(1_003_600), 1);
+        assert_eq!(vault.vested_payments(1_000_000 + 5 * 3_600 + 1), 5);
+
+        // Capped at the schedule length
+        assert_eq!(vault.vested_payments(1_000_000 + 50 * 3_600), 10);
+        assert_eq!(vault.vested_amount(1_000_000 + 50 * 3_600), 1_000);
+
+        // A short last payment still counts and the amount caps at the total
+        let uneven = StreamVault { total_amount: 950, ..vault };
+        assert_eq!(uneven.num_payments(), 10);
+        assert_eq!(uneven.vested_amount(1_000_000 + 50 * 3_600), 950);
+    }
+
+    #[test]
+    fn jittered_payments_vest_in_order_within_variance() {
+        for seed in [1, 7, 0xdead_beef, u64::MAX] {
+            let vault = stream(StreamVault::MAX_VARIANCE_BPS, seed);
+            for index in 1..=10u64 {
+                let due = vault.payment_due_at(index);
+                let scheduled = 1_000_000 + index as i64 * 3_600;
+                assert!((due - scheduled).abs() <= 900);
+
+                // Exactly the payments up to `index` are vested once it is due
+                assert_eq!(vault.vested_payments(due), index);
+                assert_eq!(vault.vested_payments(due - 1), index - 1);
+            }
+        }
+    }
+
+    #[test]
+    fn claimable_excludes_claimed() {
+        let mut vault = stream(0, 0);
+        vault.total_claimed = 200;
+        assert_eq!(vault.claimable_amount(1_000_000 + 3 * 3_600), 100);
+        assert_eq!(vault.claimable_amount(1_000_000 + 3_600), 0);
+
+        // Zero interval never vests
+        vault.interval_seconds = 0;
+        assert_eq!(vault.vested_payments(2_000_000), 0);
+    }
+}
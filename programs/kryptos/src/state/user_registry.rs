@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+/// Index of the vaults a user owns, so clients can list them with one fetch
+///
+/// Created on the user's first `initialize_dca` or `create_intent`, grown by
+/// one address per new vault and shrunk again when a vault is closed. Vaults
+/// created before registries existed are not listed.
+#[account]
+#[derive(Default)]
+pub struct UserRegistry {
+    /// User the registry belongs to
+    pub authority: Pubkey,
+    /// Lowest intent nonce above every nonce this user has used
+    pub next_intent_nonce: u64,
+    /// DCA vaults ever created through the registry
+    pub dcas_created: u32,
+    /// Intent vaults ever created through the registry
+    pub intents_created: u32,
+    pub bump: u8,
+    /// Open DCA vaults
+    pub dca_vaults: Vec<Pubkey>,
+    /// Open intent vaults
+    pub intent_vaults: Vec<Pubkey>,
+}
+
+impl UserRegistry {
+    /// PDA seeds prefix
+    pub const SEED_PREFIX: &'static [u8] = b"user_registry";
+
+    /// Account space for `dca_count` DCA and `intent_count` intent vaults
+    pub const fn space(dca_count: usize, intent_count: usize) -> usize {
+        8 +     // discriminator
+        32 +    // authority
+        8 +     // next_intent_nonce
+        4 +     // dcas_created
+        4 +     // intents_created
+        1 +     // bump
+        4 + 32 * dca_count +    // dca_vaults
+        4 + 32 * intent_count   // intent_vaults
+    }
+
+    /// Account space of the vaults currently registered
+    pub fn space_used(&self) -> usize {
+        Self::space(self.dca_vaults.len(), self.intent_vaults.len())
+    }
+
+    /// Account space once one more vault is registered
+    pub fn space_after_push(&self, dca: bool) -> usize {
+        let (dca_count, intent_count) = (self.dca_vaults.len(), self.intent_vaults.len());
+        if dca {
+            Self::space(dca_count + 1, intent_count)
+        } else {
+            Self::space(dca_count, intent_count + 1)
+        }
+    }
+
+    /// Record a new DCA vault
+    pub fn register_dca(&mut self, vault: Pubkey) {
+        self.dca_vaults.push(vault);
+        self.dcas_created = self.dcas_created.saturating_add(1);
+    }
+
+    /// Record a new intent vault and reserve its nonce
+    pub fn register_intent(&mut self, vault: Pubkey, nonce: u64) {
+        self.intent_vaults.push(vault);
+        self.intents_created = self.intents_created.saturating_add(1);
+        self.next_intent_nonce = self.next_intent_nonce.max(nonce.saturating_add(1));
+    }
+
+    /// Forget a closed DCA vault (no-op if it was never registered)
+    pub fn remove_dca(&mut self, vault: &Pubkey) {
+        self.dca_vaults.retain(|v| v != vault);
+    }
+
+    /// Forget a closed intent vault (no-op if it was never registered)
+    pub fn remove_intent(&mut self, vault: &Pubkey) {
+        self.intent_vaults.retain(|v| v != vault);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_vaults_and_nonces() {
+        let mut registry = UserRegistry::default();
+        let mut data = Vec::new();
+        registry.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), UserRegistry::space(0, 0));
+
+        let (dca, intent_a, intent_b) = (
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+            Pubkey::new_from_array([3; 32]),
+        );
+        assert_eq!(registry.space_after_push(true), UserRegistry::space(1, 0));
+        registry.register_dca(dca);
+        registry.register_intent(intent_a, 7);
+        registry.register_intent(intent_b, 3);
+        assert_eq!(registry.next_intent_nonce, 8);
+
+        let mut data = Vec::new();
+        registry.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), UserRegistry::space(1, 2));
+
+        // Removing keeps the lifetime counters and the nonce
+        registry.remove_intent(&intent_a);
+        registry.remove_dca(&dca);
+        registry.remove_dca(&dca);
+        assert_eq!(registry.intent_vaults, vec![intent_b]);
+        assert!(registry.dca_vaults.is_empty());
+        assert_eq!((registry.dcas_created, registry.intents_created), (1, 2));
+        assert_eq!(registry.next_intent_nonce, 8);
+
+        // Closing shrinks the account to what is left
+        let mut data = Vec::new();
+        registry.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), registry.space_used());
+        assert_eq!(registry.space_used(), UserRegistry::space(0, 1));
+    }
+}
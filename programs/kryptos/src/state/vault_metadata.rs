@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+/// Maximum size of an encrypted metadata blob (header + ciphertext)
+pub const MAX_METADATA_LEN: usize = 4096;
+
+/// Encrypted strategy details attached to a DCA or intent vault
+///
+/// The program never reads `data`: it is an opaque blob in the layout
+/// documented in `crate::metadata`, decryptable by the recipients it was
+/// encrypted to (the owner and their keeper).
+#[account]
+#[derive(Default)]
+pub struct VaultMetadata {
+    /// DcaVault or IntentVault the metadata describes
+    pub vault: Pubkey,
+    /// Vault owner, the only account allowed to write
+    pub authority: Pubkey,
+    /// Timestamp of the last write
+    pub updated_at: i64,
+    pub bump: u8,
+    /// Encrypted blob (header + ciphertext)
+    pub data: Vec<u8>,
+}
+
+impl VaultMetadata {
+    /// PDA seeds prefix
+    pub const SEED_PREFIX: &'static [u8] = b"vault_metadata";
+
+    /// Account space for a blob of `len` bytes
+    pub const fn space(len: usize) -> usize {
+        8 +     // discriminator
+        32 +    // vault
+        32 +    // authority
+        8 +     // updated_at
+        1 +     // bump
+        4 +     // data length prefix
+        len     // data
+    }
+}
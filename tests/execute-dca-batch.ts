@@ -0,0 +1,292 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  AccountLayout,
+  AccountState,
+  MINT_SIZE,
+  MintLayout,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  ComputeBudgetProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+} from "@solana/web3.js";
+import { BankrunProvider, startAnchor } from "anchor-bankrun";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { expect } from "chai";
+
+const IDL = require("../target/idl/kryptos.json");
+
+const BATCH_SIZES = [1, 2, 4, 8];
+const TOTAL_AMOUNT = 1_000_000_000;
+const AMOUNT_PER_TRADE = 10_000_000;
+
+describe("execute_dca_batch", () => {
+  let context: ProgramTestContext;
+  let program: Program;
+  const keeper = Keypair.generate();
+  const inputMint = Keypair.generate().publicKey;
+  const outputMint = Keypair.generate().publicKey;
+  const keeperInputToken = Keypair.generate().publicKey;
+  const keeperOutputToken = Keypair.generate().publicKey;
+  const vaults: { dcaVault: PublicKey; inputVault: PublicKey; outputVault: PublicKey }[] = [];
+
+  function setMint(address: PublicKey) {
+    const data = Buffer.alloc(MINT_SIZE);
+    MintLayout.encode(
+      {
+        mintAuthorityOption: 0,
+        mintAuthority: PublicKey.default,
+        supply: BigInt(0),
+        decimals: 6,
+        isInitialized: true,
+        freezeAuthorityOption: 0,
+        freezeAuthority: PublicKey.default,
+      },
+      data
+    );
+    context.setAccount(address, {
+      lamports: LAMPORTS_PER_SOL,
+      data,
+      owner: TOKEN_PROGRAM_ID,
+      executable: false,
+    });
+  }
+
+  function setTokenAccount(address: PublicKey, mint: PublicKey, owner: PublicKey, amount: number) {
+    const data = Buffer.alloc(AccountLayout.span);
+    AccountLayout.encode(
+      {
+        mint,
+        owner,
+        amount: BigInt(amount),
+        delegateOption: 0,
+        delegate: PublicKey.default,
+        state: AccountState.Initialized,
+        isNativeOption: 0,
+        isNative: BigInt(0),
+        delegatedAmount: BigInt(0),
+        closeAuthorityOption: 0,
+        closeAuthority: PublicKey.default,
+      },
+      data
+    );
+    context.setAccount(address, {
+      lamports: LAMPORTS_PER_SOL,
+      data,
+      owner: TOKEN_PROGRAM_ID,
+      executable: false,
+    });
+  }
+
+  function fund(address: PublicKey) {
+    context.setAccount(address, {
+      lamports: 10 * LAMPORTS_PER_SOL,
+      data: Buffer.alloc(0),
+      owner: SystemProgram.programId,
+      executable: false,
+    });
+  }
+
+  async function computeUnits(ix: anchor.web3.TransactionInstruction): Promise<number> {
+    const tx = new Transaction().add(
+      ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
+      ix
+    );
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = keeper.publicKey;
+    tx.sign(keeper);
+
+    const result = await context.banksClient.simulateTransaction(tx);
+    expect(result.result, result.meta?.logMessages.join("\n")).to.be.null;
+    return Number(result.meta!.computeUnitsConsumed);
+  }
+
+  // Stats shard of the input mint, created by the first initialize_dca
+  const [protocolStats] = PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_stats"), inputMint.toBuffer()],
+    new PublicKey(IDL.address)
+  );
+
+  async function executeDcaIx(index: number) {
+    const vault = vaults[index];
+    return program.methods
+      .executeDca({ swapAmount: new BN(AMOUNT_PER_TRADE), receivedAmount: new BN(1_000) })
+      .accountsPartial({
+        keeper: keeper.publicKey,
+        dcaVault: vault.dcaVault,
+        vaultInputToken: vault.inputVault,
+        vaultOutputToken: vault.outputVault,
+        keeperInputToken,
+        keeperOutputToken,
+        authorityOutputToken: null,
+        inputPriceUpdate: null,
+        outputPriceUpdate: null,
+        protocolStats,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  async function executeBatchIx(count: number) {
+    const batch = vaults.slice(0, count);
+    return program.methods
+      .executeDcaBatch({
+        swapAmounts: batch.map(() => new BN(AMOUNT_PER_TRADE)),
+        totalReceived: new BN(1_000 * count),
+      })
+      .accountsPartial({
+        keeper: keeper.publicKey,
+        keeperInputToken,
+        keeperOutputToken,
+        protocolStats,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        batch.flatMap((vault) => [
+          { pubkey: vault.dcaVault, isWritable: true, isSigner: false },
+          { pubkey: vault.inputVault, isWritable: true, isSigner: false },
+          { pubkey: vault.outputVault, isWritable: true, isSigner: false },
+        ])
+      )
+      .instruction();
+  }
+
+  before(async () => {
+    context = await startAnchor("", [], []);
+    const provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program(IDL, provider);
+
+    fund(keeper.publicKey);
+    setMint(inputMint);
+    setMint(outputMint);
+    setTokenAccount(keeperInputToken, inputMint, keeper.publicKey, 0);
+    setTokenAccount(keeperOutputToken, outputMint, keeper.publicKey, 1_000_000_000);
+
+    // One vault per user, all on the same mint pair
+    const maxBatch = Math.max(...BATCH_SIZES);
+    for (let i = 0; i < maxBatch; i++) {
+      const user = Keypair.generate();
+      const userInputToken = Keypair.generate().publicKey;
+      fund(user.publicKey);
+      setTokenAccount(userInputToken, inputMint, user.publicKey, TOTAL_AMOUNT);
+
+      const [dcaVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dca_vault"), user.publicKey.toBuffer(), inputMint.toBuffer(), outputMint.toBuffer()],
+        program.programId
+      );
+      const [inputVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("input_vault"), dcaVault.toBuffer()],
+        program.programId
+      );
+      const [outputVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("output_vault"), dcaVault.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeDca({
+          totalAmount: new BN(TOTAL_AMOUNT),
+          amountPerTrade: new BN(AMOUNT_PER_TRADE),
+          varianceBps: 0,
+          minExecutions: 7,
+          maxExecutions: 7,
+          windowStartHour: 0,
+          windowEndHour: 23,
+          outputFeedId: Array(32).fill(0),
+          minPrice: new BN(0),
+          maxPrice: new BN(0),
+          strategy: { fixedAmount: {} },
+          inputFeedId: Array(32).fill(0),
+          targetIncrement: new BN(0),
+          minTradeAmount: new BN(0),
+          maxTradeAmount: new BN(0),
+          side: { buy: {} },
+          maxSlippageBps: 0,
+          privacyLevel: { public: {} },
+        })
+        .accountsPartial({
+          authority: user.publicKey,
+          dcaVault,
+          inputMint,
+          outputMint,
+          userInputToken,
+          vaultInputToken: inputVault,
+          vaultOutputToken: outputVault,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+
+      vaults.push({ dcaVault, inputVault, outputVault });
+    }
+
+    // Move past the first scheduled execution
+    const clock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(2 * 3600)
+      )
+    );
+  });
+
+  it("benchmarks compute units against one execute_dca per vault", async () => {
+    const single = await computeUnits(await executeDcaIx(0));
+    console.log(`      execute_dca: ${single} CU`);
+
+    for (const count of BATCH_SIZES) {
+      const batch = await computeUnits(await executeBatchIx(count));
+      const perVault = Math.round(batch / count);
+      console.log(
+        `      execute_dca_batch x${count}: ${batch} CU (${perVault} CU/vault, ` +
+          `${single * count} CU as separate instructions)`
+      );
+      expect(batch).to.be.lessThan(1_400_000);
+    }
+  });
+
+  it("settles every vault from one netted swap", async () => {
+    const count = 4;
+    const tx = new Transaction().add(
+      ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
+      await executeBatchIx(count)
+    );
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = keeper.publicKey;
+    tx.sign(keeper);
+    await context.banksClient.processTransaction(tx);
+
+    for (const vault of vaults.slice(0, count)) {
+      const account: any = await program.account.dcaVault.fetch(vault.dcaVault);
+      expect(account.totalSpent.toNumber()).to.equal(AMOUNT_PER_TRADE);
+      expect(account.totalReceived.toNumber()).to.equal(1_000);
+      expect(account.executionCount).to.equal(1);
+    }
+
+    // The input mint's stats shard counts every vault and execution
+    const stats: any = await program.account.protocolStats.fetch(protocolStats);
+    expect(stats.activeDcas).to.equal(vaults.length);
+    expect(stats.totalExecutions.toNumber()).to.equal(count);
+    expect(stats.totalVolume.toNumber()).to.equal(AMOUNT_PER_TRADE * count);
+
+    // Executed vaults are rescheduled and cannot be batched again right away
+    const again = new Transaction().add(await executeBatchIx(1));
+    again.recentBlockhash = context.lastBlockhash;
+    again.feePayer = keeper.publicKey;
+    again.sign(keeper);
+    const result = await context.banksClient.simulateTransaction(again);
+    expect(result.result).to.not.be.null;
+  });
+});